@@ -41,7 +41,8 @@ impl Command {
             Command::Run { infile, outfile } => {
                 let content = infile.read_to_string()?;
                 let tnetnoc = content.chars().rev().collect::<String>();
-                outfile.write(tnetnoc)
+                outfile.write(tnetnoc)?;
+                Ok(())
             }
             Command::Help => {
                 println!("Usage: revchars [<options>] [<path>]");
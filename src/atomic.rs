@@ -0,0 +1,152 @@
+#[cfg(unix)]
+use crate::FdArg;
+use crate::{Error, Operation, OutputArg};
+use std::ffi::OsString;
+use std::fs;
+use std::io::{self, StdoutLock, Write};
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+
+/// The type of the writers returned by [`OutputArg::create_atomic()`].
+///
+/// When the output arg is the `Stdout` variant (or, on Unix, the `Fd`
+/// variant), data is written directly to stdout (or the file descriptor).
+/// Otherwise, data is written to a temporary file in the same
+/// directory as the target path, and the temporary file is only renamed over
+/// the target once [`commit()`][AtomicOutputArgWriter::commit] is called.  If
+/// the writer is dropped without being committed, the temporary file is
+/// deleted and the target path is left untouched.
+///
+/// This type implements [`std::io::Write`].
+///
+/// [`OutputArg::create_atomic()`]: crate::OutputArg::create_atomic
+#[derive(Debug)]
+pub struct AtomicOutputArgWriter(Inner);
+
+#[derive(Debug)]
+enum Inner {
+    Stdout(StdoutLock<'static>),
+    #[cfg(unix)]
+    Fd {
+        file: fs::File,
+        fd: FdArg,
+    },
+    File {
+        tmpfile: NamedTempFile,
+        path: PathBuf,
+    },
+}
+
+impl AtomicOutputArgWriter {
+    pub(crate) fn stdout() -> AtomicOutputArgWriter {
+        AtomicOutputArgWriter(Inner::Stdout(io::stdout().lock()))
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn file(file: fs::File, fd: FdArg) -> AtomicOutputArgWriter {
+        AtomicOutputArgWriter(Inner::Fd { file, fd })
+    }
+
+    pub(crate) fn create(path: &Path) -> io::Result<AtomicOutputArgWriter> {
+        let dir = match path.parent() {
+            Some(p) if !p.as_os_str().is_empty() => p,
+            _ => Path::new("."),
+        };
+        let mut prefix = OsString::from(".");
+        if let Some(name) = path.file_name() {
+            prefix.push(name);
+        }
+        prefix.push(".");
+        let mut builder = tempfile::Builder::new();
+        builder.prefix(&prefix).suffix(".tmp");
+        // Create the file with the same default mode as `File::create()`
+        // (subject to the umask) rather than tempfile's 0600, so that a new
+        // target ends up with the same permissions as a non-atomic write.
+        #[cfg(unix)]
+        builder.permissions(fs::Permissions::from_mode(0o666));
+        let tmpfile = builder.tempfile_in(dir)?;
+        Ok(AtomicOutputArgWriter(Inner::File {
+            tmpfile,
+            path: path.to_path_buf(),
+        }))
+    }
+
+    /// Finish writing and move the written data into place.
+    ///
+    /// If the output arg is the `Stdout` or `Fd` variant, this just flushes
+    /// the output.
+    /// Otherwise, the temporary file is flushed & synced to disk, given the
+    /// same permissions as any file already at the target path (if there is
+    /// no such file, the temporary file was created with the same default
+    /// permissions as [`std::fs::File::create`] uses), and then
+    /// renamed over the target path, replacing that file.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`std::io::Write::flush`],
+    /// [`std::fs::File::sync_all`], [`std::fs::metadata`],
+    /// [`std::fs::File::set_permissions`], and
+    /// [`tempfile::NamedTempFile::persist`].
+    /// If an error occurs, the temporary file is deleted and the target path
+    /// is left untouched.
+    pub fn commit(self) -> Result<(), Error> {
+        match self.0 {
+            Inner::Stdout(mut stdout) => stdout
+                .flush()
+                .map_err(|e| Error::output(Operation::Write, &OutputArg::Stdout, e)),
+            #[cfg(unix)]
+            Inner::Fd { mut file, fd } => file
+                .flush()
+                .map_err(|e| Error::output(Operation::Write, &OutputArg::Fd(fd), e)),
+            Inner::File { mut tmpfile, path } => {
+                let r = tmpfile
+                    .flush()
+                    .and_then(|()| tmpfile.as_file().sync_all())
+                    .and_then(|()| copy_permissions(&path, tmpfile.as_file()))
+                    .and_then(|()| tmpfile.persist(&path).map_err(|e| e.error));
+                r.map(|_| ())
+                    .map_err(|e| Error::output(Operation::Commit, &OutputArg::Path(path), e))
+            }
+        }
+    }
+}
+
+impl Write for AtomicOutputArgWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.0 {
+            Inner::Stdout(stdout) => stdout.write(buf),
+            #[cfg(unix)]
+            Inner::Fd { file, .. } => file.write(buf),
+            Inner::File { tmpfile, .. } => tmpfile.write(buf),
+        }
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        match &mut self.0 {
+            Inner::Stdout(stdout) => stdout.write_all(buf),
+            #[cfg(unix)]
+            Inner::Fd { file, .. } => file.write_all(buf),
+            Inner::File { tmpfile, .. } => tmpfile.write_all(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.0 {
+            Inner::Stdout(stdout) => stdout.flush(),
+            #[cfg(unix)]
+            Inner::Fd { file, .. } => file.flush(),
+            Inner::File { tmpfile, .. } => tmpfile.flush(),
+        }
+    }
+}
+
+/// If a file exists at `path`, give `file` the same permissions
+fn copy_permissions(path: &Path, file: &fs::File) -> io::Result<()> {
+    match fs::metadata(path) {
+        Ok(md) => file.set_permissions(md.permissions()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
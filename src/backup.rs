@@ -0,0 +1,186 @@
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Policies for backing up an existing file before it is overwritten,
+/// following the `--backup` option of GNU coreutils.
+///
+/// A policy is applied to an output arg by passing it to
+/// [`OutputArg::create_with_backup()`][crate::OutputArg::create_with_backup]
+/// or [`OutputArgOptions::backup()`][crate::OutputArgOptions::backup].
+/// Backups are made by renaming the existing file, so the output arg is then
+/// written to as a new file.
+///
+/// `BackupPolicy` implements `FromStr`, accepting the same values as GNU's
+/// `--backup=CONTROL`: `none`/`off`, `simple`/`never`, `numbered`/`t`, and
+/// `existing`/`nil`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum BackupPolicy {
+    /// Never make backups.
+    ///
+    /// This is the variant returned by `BackupPolicy::default()`.
+    #[default]
+    None,
+
+    /// Make a simple backup by appending the backup suffix (`~` by default)
+    /// to the file name, replacing any previous simple backup
+    Simple,
+
+    /// Make a numbered backup named `{name}.~N~`, where `N` is one more than
+    /// the highest number of any existing numbered backup of the file
+    Numbered,
+
+    /// Make a numbered backup if the file already has numbered backups, or a
+    /// simple backup otherwise
+    Existing,
+}
+
+impl BackupPolicy {
+    /// Determine the path that a backup of `path` would be made at under
+    /// this policy, using `suffix` for simple backups.  Returns `None` if the
+    /// policy is [`BackupPolicy::None`].
+    ///
+    /// # Errors
+    ///
+    /// For the `Numbered` and `Existing` policies, has the same error
+    /// conditions as [`std::fs::read_dir`] on the directory containing
+    /// `path`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use patharg::BackupPolicy;
+    /// use std::path::{Path, PathBuf};
+    ///
+    /// assert_eq!(
+    ///     BackupPolicy::Simple.backup_path(Path::new("foo.txt"), "~").unwrap(),
+    ///     Some(PathBuf::from("foo.txt~"))
+    /// );
+    /// ```
+    pub fn backup_path<S: AsRef<OsStr>>(
+        self,
+        path: &Path,
+        suffix: S,
+    ) -> io::Result<Option<PathBuf>> {
+        match self {
+            BackupPolicy::None => Ok(None),
+            BackupPolicy::Simple => Ok(Some(simple_backup_path(path, suffix.as_ref()))),
+            BackupPolicy::Numbered => {
+                let n = highest_backup_number(path)?.map_or(1, |n| n + 1);
+                Ok(Some(numbered_backup_path(path, n)))
+            }
+            BackupPolicy::Existing => match highest_backup_number(path)? {
+                Some(n) => Ok(Some(numbered_backup_path(path, n + 1))),
+                None => Ok(Some(simple_backup_path(path, suffix.as_ref()))),
+            },
+        }
+    }
+}
+
+impl FromStr for BackupPolicy {
+    type Err = ParseBackupPolicyError;
+
+    fn from_str(s: &str) -> Result<BackupPolicy, ParseBackupPolicyError> {
+        match s {
+            "none" | "off" => Ok(BackupPolicy::None),
+            "simple" | "never" => Ok(BackupPolicy::Simple),
+            "numbered" | "t" => Ok(BackupPolicy::Numbered),
+            "existing" | "nil" => Ok(BackupPolicy::Existing),
+            _ => Err(ParseBackupPolicyError),
+        }
+    }
+}
+
+/// Error returned when parsing an invalid [`BackupPolicy`] string
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ParseBackupPolicyError;
+
+impl fmt::Display for ParseBackupPolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid backup policy; expected one of none, off, simple, never, numbered, t, existing, or nil"
+        )
+    }
+}
+
+impl std::error::Error for ParseBackupPolicyError {}
+
+/// If `path` exists, rename it to a backup path determined by `policy` and
+/// `suffix`, and return the backup path
+pub(crate) fn make_backup(
+    policy: BackupPolicy,
+    suffix: &OsStr,
+    path: &Path,
+) -> io::Result<Option<PathBuf>> {
+    if policy == BackupPolicy::None {
+        return Ok(None);
+    }
+    match fs::symlink_metadata(path) {
+        Ok(_) => (),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let backup = policy.backup_path(path, suffix)?;
+    if let Some(ref backup) = backup {
+        fs::rename(path, backup)?;
+    }
+    Ok(backup)
+}
+
+/// Undo [`make_backup()`] after opening `path` failed by renaming the backup,
+/// if one was made, back to `path`.  This is best-effort, as the original
+/// error is more useful to the caller than any error from the rename.
+pub(crate) fn restore_backup(backup: Option<PathBuf>, path: &Path) {
+    if let Some(backup) = backup {
+        let _ = fs::rename(backup, path);
+    }
+}
+
+fn simple_backup_path(path: &Path, suffix: &OsStr) -> PathBuf {
+    let mut s = path.as_os_str().to_owned();
+    s.push(suffix);
+    PathBuf::from(s)
+}
+
+fn numbered_backup_path(path: &Path, n: u64) -> PathBuf {
+    let mut s = path.as_os_str().to_owned();
+    s.push(format!(".~{n}~"));
+    PathBuf::from(s)
+}
+
+/// Return the highest `N` among existing files named `{name}.~N~` in the same
+/// directory as `path`
+fn highest_backup_number(path: &Path) -> io::Result<Option<u64>> {
+    let Some(name) = path.file_name() else {
+        return Ok(None);
+    };
+    let dir = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    let mut prefix = OsString::from(name);
+    prefix.push(".~");
+    let Some(prefix) = prefix.to_str().map(String::from) else {
+        return Ok(None);
+    };
+    let mut highest = None;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let Ok(fname) = entry.file_name().into_string() else {
+            continue;
+        };
+        if let Some(n) = fname
+            .strip_prefix(&prefix)
+            .and_then(|s| s.strip_suffix('~'))
+            .filter(|s| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|s| s.parse::<u64>().ok())
+        {
+            highest = highest.max(Some(n));
+        }
+    }
+    Ok(highest)
+}
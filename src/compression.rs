@@ -0,0 +1,675 @@
+use crate::{Error, InputArg, InputArgReader, Operation, OutputArg, OutputArgWriter};
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Cursor, Read, Write};
+use std::path::Path;
+
+#[cfg(feature = "tokio")]
+use crate::AsyncOutputArgWriter;
+#[cfg(feature = "tokio")]
+use std::pin::Pin;
+#[cfg(feature = "tokio")]
+use std::task::{Context, Poll};
+#[cfg(feature = "tokio")]
+use tokio::io::AsyncWrite;
+
+/// A compression format supported by the `compression` feature
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
+pub enum Compression {
+    /// gzip compression, as produced by `gzip`
+    Gzip,
+
+    /// bzip2 compression, as produced by `bzip2`
+    Bzip2,
+
+    /// xz compression, as produced by `xz`
+    Xz,
+
+    /// Zstandard compression, as produced by `zstd`
+    Zstd,
+}
+
+/// The length of the longest magic number recognized by
+/// [`Compression::from_magic()`]
+const MAX_MAGIC_LEN: u64 = 6;
+
+impl Compression {
+    /// Determine the compression format indicated by a path's file extension.
+    ///
+    /// The recognized extensions are `.gz`, `.bz2`, `.xz`, and `.zst`.  If
+    /// the path has any other extension or no extension, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use patharg::Compression;
+    /// use std::path::Path;
+    ///
+    /// assert_eq!(
+    ///     Compression::from_extension(Path::new("data.jsonl.gz")),
+    ///     Some(Compression::Gzip)
+    /// );
+    /// assert_eq!(Compression::from_extension(Path::new("data.jsonl")), None);
+    /// ```
+    pub fn from_extension(path: &Path) -> Option<Compression> {
+        match path.extension()?.to_str()? {
+            "gz" => Some(Compression::Gzip),
+            "bz2" => Some(Compression::Bzip2),
+            "xz" => Some(Compression::Xz),
+            "zst" => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+
+    /// Determine the compression format of some data by examining its
+    /// initial "magic" bytes.
+    ///
+    /// If `bytes` does not start with the magic bytes of any supported
+    /// format, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use patharg::Compression;
+    ///
+    /// assert_eq!(
+    ///     Compression::from_magic(b"\x1F\x8B\x08\x00"),
+    ///     Some(Compression::Gzip)
+    /// );
+    /// assert_eq!(Compression::from_magic(b"plain text"), None);
+    /// ```
+    pub fn from_magic(bytes: &[u8]) -> Option<Compression> {
+        if bytes.starts_with(b"\x1F\x8B") {
+            Some(Compression::Gzip)
+        } else if bytes.starts_with(b"BZh") {
+            Some(Compression::Bzip2)
+        } else if bytes.starts_with(b"\xFD7zXZ\x00") {
+            Some(Compression::Xz)
+        } else if bytes.starts_with(b"\x28\xB5\x2F\xFD") {
+            Some(Compression::Zstd)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
+impl InputArg {
+    /// Open the input arg for reading, transparently decompressing it if it
+    /// is compressed.
+    ///
+    /// The input arg is opened as with [`InputArg::open()`], and then its
+    /// compression format (if any) is determined: if the input arg is a
+    /// `Path` variant whose path has a file extension recognized by
+    /// [`Compression::from_extension()`], that format is used; otherwise, the
+    /// first bytes of the input (including for stdin) are examined with
+    /// [`Compression::from_magic()`].  If no compression format is detected,
+    /// the input is read as-is.
+    ///
+    /// The returned reader implements [`std::io::BufRead`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::open()`] and
+    /// [`std::io::Read::read`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::InputArg;
+    /// use std::env::args_os;
+    /// use std::io::{self, Read};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let infile = args_os().nth(1)
+    ///                           .map(InputArg::from_arg)
+    ///                           .unwrap_or_default();
+    ///     let mut f = infile.open_decompressed()?;
+    ///     let mut buffer = [0; 16];
+    ///     let n = f.read(&mut buffer)?;
+    ///     println!("First {} decompressed bytes: {:?}", n, &buffer[..n]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn open_decompressed(&self) -> Result<DecompressedReader, Error> {
+        let mut reader = self.open()?;
+        let mut head = Vec::new();
+        let compression =
+            if let Some(c) = self.path_ref().and_then(|p| Compression::from_extension(p)) {
+                Some(c)
+            } else {
+                // A single `fill_buf()` may return fewer bytes than the
+                // longest magic number (e.g., when reading from a pipe), so
+                // keep reading until there are enough bytes or EOF.
+                (&mut reader)
+                    .take(MAX_MAGIC_LEN)
+                    .read_to_end(&mut head)
+                    .map_err(|e| Error::input(Operation::Read, self, e))?;
+                Compression::from_magic(&head)
+            };
+        DecompressedReader::new(Cursor::new(head).chain(reader), compression)
+            .map_err(|e| Error::input(Operation::Open, self, e))
+    }
+
+    /// Read the entire contents of the input arg into a bytes vector,
+    /// transparently decompressing it if it is compressed.
+    ///
+    /// Compression is detected as described for
+    /// [`InputArg::open_decompressed()`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::open_decompressed()`] and
+    /// [`std::io::Read::read_to_end`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::InputArg;
+    /// use std::env::args_os;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let infile = args_os().nth(1)
+    ///                           .map(InputArg::from_arg)
+    ///                           .unwrap_or_default();
+    ///     let input = infile.read_decompressed()?;
+    ///     println!("Read {} decompressed bytes from input", input.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn read_decompressed(&self) -> Result<Vec<u8>, Error> {
+        let mut vec = Vec::new();
+        self.open_decompressed()?
+            .read_to_end(&mut vec)
+            .map_err(|e| Error::input(Operation::Read, self, e))?;
+        Ok(vec)
+    }
+
+    /// Return an iterator over the lines of the input arg, transparently
+    /// decompressing it if it is compressed.
+    ///
+    /// Compression is detected as described for
+    /// [`InputArg::open_decompressed()`].
+    ///
+    /// The returned iterator yields instances of `std::io::Result<String>`,
+    /// where each individual item has the same error conditions as
+    /// [`std::io::BufRead::read_line()`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::open_decompressed()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::InputArg;
+    /// use std::env::args_os;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let infile = args_os().nth(1)
+    ///                           .map(InputArg::from_arg)
+    ///                           .unwrap_or_default();
+    ///     for (i, r) in infile.lines_decompressed()?.enumerate() {
+    ///         let line = r?;
+    ///         println!("Line {} is {} characters long.", i + 1, line.len());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn lines_decompressed(&self) -> Result<DecompressedLines, Error> {
+        Ok(self.open_decompressed()?.lines())
+    }
+}
+
+/// The type of the readers returned by [`InputArg::open_decompressed()`].
+///
+/// This type implements [`std::io::BufRead`].
+#[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
+pub struct DecompressedReader(Decoder);
+
+/// An input arg's reader preceded by the bytes that were read from it in
+/// order to detect its compression format
+type SniffedReader = io::Chain<Cursor<Vec<u8>>, InputArgReader>;
+
+enum Decoder {
+    Plain(SniffedReader),
+    Gzip(BufReader<flate2::bufread::MultiGzDecoder<SniffedReader>>),
+    Bzip2(BufReader<bzip2::bufread::MultiBzDecoder<SniffedReader>>),
+    Xz(BufReader<liblzma::bufread::XzDecoder<SniffedReader>>),
+    Zstd(BufReader<zstd::Decoder<'static, SniffedReader>>),
+}
+
+impl DecompressedReader {
+    fn new(
+        reader: SniffedReader,
+        compression: Option<Compression>,
+    ) -> io::Result<DecompressedReader> {
+        let decoder = match compression {
+            None => Decoder::Plain(reader),
+            Some(Compression::Gzip) => {
+                Decoder::Gzip(BufReader::new(flate2::bufread::MultiGzDecoder::new(reader)))
+            }
+            Some(Compression::Bzip2) => {
+                Decoder::Bzip2(BufReader::new(bzip2::bufread::MultiBzDecoder::new(reader)))
+            }
+            Some(Compression::Xz) => Decoder::Xz(BufReader::new(
+                liblzma::bufread::XzDecoder::new_multi_decoder(reader),
+            )),
+            Some(Compression::Zstd) => {
+                Decoder::Zstd(BufReader::new(zstd::Decoder::with_buffer(reader)?))
+            }
+        };
+        Ok(DecompressedReader(decoder))
+    }
+
+    /// Returns the compression format that the reader is decompressing, or
+    /// `None` if the input was not detected as compressed
+    pub fn compression(&self) -> Option<Compression> {
+        match self.0 {
+            Decoder::Plain(_) => None,
+            Decoder::Gzip(_) => Some(Compression::Gzip),
+            Decoder::Bzip2(_) => Some(Compression::Bzip2),
+            Decoder::Xz(_) => Some(Compression::Xz),
+            Decoder::Zstd(_) => Some(Compression::Zstd),
+        }
+    }
+}
+
+impl fmt::Debug for DecompressedReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecompressedReader")
+            .field("compression", &self.compression())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Read for DecompressedReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match &mut self.0 {
+            Decoder::Plain(r) => r.read(buf),
+            Decoder::Gzip(r) => r.read(buf),
+            Decoder::Bzip2(r) => r.read(buf),
+            Decoder::Xz(r) => r.read(buf),
+            Decoder::Zstd(r) => r.read(buf),
+        }
+    }
+}
+
+impl BufRead for DecompressedReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match &mut self.0 {
+            Decoder::Plain(r) => r.fill_buf(),
+            Decoder::Gzip(r) => r.fill_buf(),
+            Decoder::Bzip2(r) => r.fill_buf(),
+            Decoder::Xz(r) => r.fill_buf(),
+            Decoder::Zstd(r) => r.fill_buf(),
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        match &mut self.0 {
+            Decoder::Plain(r) => r.consume(amt),
+            Decoder::Gzip(r) => r.consume(amt),
+            Decoder::Bzip2(r) => r.consume(amt),
+            Decoder::Xz(r) => r.consume(amt),
+            Decoder::Zstd(r) => r.consume(amt),
+        }
+    }
+}
+
+/// The type of the iterators returned by [`InputArg::lines_decompressed()`].
+///
+/// This iterator yields instances of `std::io::Result<String>`.
+#[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
+pub type DecompressedLines = io::Lines<DecompressedReader>;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
+impl OutputArg {
+    /// Open the output arg for writing, compressing the written data
+    /// according to the output path's file extension.
+    ///
+    /// If the output arg is a `Path` variant whose path has a file extension
+    /// recognized by [`Compression::from_extension()`], data written to the
+    /// returned writer is compressed with the corresponding format before
+    /// being written to the path.  Otherwise — including when the output arg
+    /// is the `Stdout` variant — data is written uncompressed; use
+    /// [`OutputArg::create_with_compression()`] to compress data written to
+    /// stdout.
+    ///
+    /// The output arg is opened as with [`OutputArg::create()`].  The returned
+    /// writer implements [`std::io::Write`].  Once all data has been written,
+    /// [`CompressedWriter::finish()`] should be called in order to write the
+    /// end of the compressed stream and detect any errors in doing so.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`OutputArg::create()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::OutputArg;
+    /// use std::env::args_os;
+    /// use std::io::{self, Write};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let outfile = args_os().nth(1)
+    ///                            .map(OutputArg::from_arg)
+    ///                            .unwrap_or_default();
+    ///     let mut f = outfile.create_compressed()?;
+    ///     writeln!(&mut f, "This line may be compressed.")?;
+    ///     f.finish()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn create_compressed(&self) -> Result<CompressedWriter, Error> {
+        self.create_with_compression(self.path_ref().and_then(|p| Compression::from_extension(p)))
+    }
+
+    /// Open the output arg for writing, compressing the written data with the
+    /// given compression format.
+    ///
+    /// If `compression` is `None`, data is written uncompressed.  The output
+    /// path's file extension (if any) is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`OutputArg::create()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::{Compression, OutputArg};
+    /// use std::io::{self, Write};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut f = OutputArg::Stdout.create_with_compression(Some(Compression::Gzip))?;
+    ///     writeln!(&mut f, "This line will be gzipped.")?;
+    ///     f.finish()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn create_with_compression(
+        &self,
+        compression: Option<Compression>,
+    ) -> Result<CompressedWriter, Error> {
+        let writer = self.create()?;
+        CompressedWriter::new(writer, compression, self.clone())
+            .map_err(|e| Error::output(Operation::Create, self, e))
+    }
+}
+
+/// The type of the writers returned by [`OutputArg::create_compressed()`] and
+/// [`OutputArg::create_with_compression()`].
+///
+/// This type implements [`std::io::Write`].  Dropping the writer finishes the
+/// compressed stream, but any errors that occur in doing so are ignored; call
+/// [`finish()`][CompressedWriter::finish] to detect them.
+#[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
+pub struct CompressedWriter {
+    encoder: Encoder,
+    arg: OutputArg,
+}
+
+enum Encoder {
+    Plain(OutputArgWriter),
+    Gzip(flate2::write::GzEncoder<OutputArgWriter>),
+    Bzip2(bzip2::write::BzEncoder<OutputArgWriter>),
+    Xz(liblzma::write::XzEncoder<OutputArgWriter>),
+    Zstd(zstd::Encoder<'static, OutputArgWriter>),
+}
+
+impl CompressedWriter {
+    fn new(
+        writer: OutputArgWriter,
+        compression: Option<Compression>,
+        arg: OutputArg,
+    ) -> io::Result<CompressedWriter> {
+        let encoder = match compression {
+            None => Encoder::Plain(writer),
+            Some(Compression::Gzip) => Encoder::Gzip(flate2::write::GzEncoder::new(
+                writer,
+                flate2::Compression::default(),
+            )),
+            Some(Compression::Bzip2) => Encoder::Bzip2(bzip2::write::BzEncoder::new(
+                writer,
+                bzip2::Compression::default(),
+            )),
+            Some(Compression::Xz) => Encoder::Xz(liblzma::write::XzEncoder::new(writer, 6)),
+            Some(Compression::Zstd) => Encoder::Zstd(zstd::Encoder::new(writer, 0)?),
+        };
+        Ok(CompressedWriter { encoder, arg })
+    }
+
+    /// Returns the compression format that the writer is compressing with,
+    /// or `None` if the data is being written uncompressed
+    pub fn compression(&self) -> Option<Compression> {
+        match self.encoder {
+            Encoder::Plain(_) => None,
+            Encoder::Gzip(_) => Some(Compression::Gzip),
+            Encoder::Bzip2(_) => Some(Compression::Bzip2),
+            Encoder::Xz(_) => Some(Compression::Xz),
+            Encoder::Zstd(_) => Some(Compression::Zstd),
+        }
+    }
+
+    /// Write the end of the compressed stream (if any) and flush the
+    /// underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing the end of the compressed stream or
+    /// flushing fails.
+    pub fn finish(mut self) -> Result<(), Error> {
+        self.try_finish()
+            .map_err(|e| Error::output(Operation::Write, &self.arg, e))
+    }
+
+    fn try_finish(&mut self) -> io::Result<()> {
+        match &mut self.encoder {
+            Encoder::Plain(w) => w.flush(),
+            Encoder::Gzip(w) => {
+                w.try_finish()?;
+                w.get_mut().flush()
+            }
+            Encoder::Bzip2(w) => {
+                w.try_finish()?;
+                w.get_mut().flush()
+            }
+            Encoder::Xz(w) => {
+                w.try_finish()?;
+                w.get_mut().flush()
+            }
+            Encoder::Zstd(w) => {
+                w.do_finish()?;
+                w.get_mut().flush()
+            }
+        }
+    }
+}
+
+impl fmt::Debug for CompressedWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompressedWriter")
+            .field("compression", &self.compression())
+            .field("arg", &self.arg)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Write for CompressedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.encoder {
+            Encoder::Plain(w) => w.write(buf),
+            Encoder::Gzip(w) => w.write(buf),
+            Encoder::Bzip2(w) => w.write(buf),
+            Encoder::Xz(w) => w.write(buf),
+            Encoder::Zstd(w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.encoder {
+            Encoder::Plain(w) => w.flush(),
+            Encoder::Gzip(w) => w.flush(),
+            Encoder::Bzip2(w) => w.flush(),
+            Encoder::Xz(w) => w.flush(),
+            Encoder::Zstd(w) => w.flush(),
+        }
+    }
+}
+
+impl Drop for CompressedWriter {
+    fn drop(&mut self) {
+        // The other encoders finish themselves when dropped, but zstd's does
+        // not.
+        if let Encoder::Zstd(w) = &mut self.encoder {
+            let _ = w.do_finish();
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "compression", feature = "tokio"))))]
+impl OutputArg {
+    /// Asynchronously open the output arg for writing, compressing the
+    /// written data according to the output path's file extension.
+    ///
+    /// Compression is selected as described for
+    /// [`OutputArg::create_compressed()`].  The output arg is opened as with
+    /// [`OutputArg::async_create()`].
+    ///
+    /// The returned writer implements [`tokio::io::AsyncWrite`].  Once all
+    /// data has been written, the writer must be shut down with
+    /// [`tokio::io::AsyncWriteExt::shutdown()`] in order to write the end of
+    /// the compressed stream; unlike [`CompressedWriter`], this cannot be done
+    /// automatically when the writer is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`OutputArg::async_create()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::OutputArg;
+    /// use std::env::args_os;
+    /// use tokio::io::AsyncWriteExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let outfile = args_os().nth(1)
+    ///                            .map(OutputArg::from_arg)
+    ///                            .unwrap_or_default();
+    ///     let mut f = outfile.async_create_compressed().await?;
+    ///     f.write_all(b"This line may be compressed.\n").await?;
+    ///     f.shutdown().await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn async_create_compressed(&self) -> Result<AsyncCompressedWriter, Error> {
+        self.async_create_with_compression(
+            self.path_ref().and_then(|p| Compression::from_extension(p)),
+        )
+        .await
+    }
+
+    /// Asynchronously open the output arg for writing, compressing the
+    /// written data with the given compression format.
+    ///
+    /// If `compression` is `None`, data is written uncompressed.  The output
+    /// path's file extension (if any) is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`OutputArg::async_create()`].
+    pub async fn async_create_with_compression(
+        &self,
+        compression: Option<Compression>,
+    ) -> Result<AsyncCompressedWriter, Error> {
+        use async_compression::tokio::write::{BzEncoder, GzipEncoder, XzEncoder, ZstdEncoder};
+        let writer = self.async_create().await?;
+        Ok(AsyncCompressedWriter(match compression {
+            None => AsyncEncoder::Plain(writer),
+            Some(Compression::Gzip) => AsyncEncoder::Gzip(GzipEncoder::new(writer)),
+            Some(Compression::Bzip2) => AsyncEncoder::Bzip2(BzEncoder::new(writer)),
+            Some(Compression::Xz) => AsyncEncoder::Xz(XzEncoder::new(writer)),
+            Some(Compression::Zstd) => AsyncEncoder::Zstd(ZstdEncoder::new(writer)),
+        }))
+    }
+}
+
+/// The type of the asynchronous writers returned by
+/// [`OutputArg::async_create_compressed()`] and
+/// [`OutputArg::async_create_with_compression()`].
+///
+/// This type implements [`tokio::io::AsyncWrite`].  Shutting down the writer
+/// writes the end of the compressed stream.
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "compression", feature = "tokio"))))]
+#[derive(Debug)]
+pub struct AsyncCompressedWriter(AsyncEncoder);
+
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+enum AsyncEncoder {
+    Plain(AsyncOutputArgWriter),
+    Gzip(async_compression::tokio::write::GzipEncoder<AsyncOutputArgWriter>),
+    Bzip2(async_compression::tokio::write::BzEncoder<AsyncOutputArgWriter>),
+    Xz(async_compression::tokio::write::XzEncoder<AsyncOutputArgWriter>),
+    Zstd(async_compression::tokio::write::ZstdEncoder<AsyncOutputArgWriter>),
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncCompressedWriter {
+    /// Returns the compression format that the writer is compressing with,
+    /// or `None` if the data is being written uncompressed
+    pub fn compression(&self) -> Option<Compression> {
+        match self.0 {
+            AsyncEncoder::Plain(_) => None,
+            AsyncEncoder::Gzip(_) => Some(Compression::Gzip),
+            AsyncEncoder::Bzip2(_) => Some(Compression::Bzip2),
+            AsyncEncoder::Xz(_) => Some(Compression::Xz),
+            AsyncEncoder::Zstd(_) => Some(Compression::Zstd),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncWrite for AsyncCompressedWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match &mut self.get_mut().0 {
+            AsyncEncoder::Plain(w) => Pin::new(w).poll_write(cx, buf),
+            AsyncEncoder::Gzip(w) => Pin::new(w).poll_write(cx, buf),
+            AsyncEncoder::Bzip2(w) => Pin::new(w).poll_write(cx, buf),
+            AsyncEncoder::Xz(w) => Pin::new(w).poll_write(cx, buf),
+            AsyncEncoder::Zstd(w) => Pin::new(w).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match &mut self.get_mut().0 {
+            AsyncEncoder::Plain(w) => Pin::new(w).poll_flush(cx),
+            AsyncEncoder::Gzip(w) => Pin::new(w).poll_flush(cx),
+            AsyncEncoder::Bzip2(w) => Pin::new(w).poll_flush(cx),
+            AsyncEncoder::Xz(w) => Pin::new(w).poll_flush(cx),
+            AsyncEncoder::Zstd(w) => Pin::new(w).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match &mut self.get_mut().0 {
+            AsyncEncoder::Plain(w) => Pin::new(w).poll_shutdown(cx),
+            AsyncEncoder::Gzip(w) => Pin::new(w).poll_shutdown(cx),
+            AsyncEncoder::Bzip2(w) => Pin::new(w).poll_shutdown(cx),
+            AsyncEncoder::Xz(w) => Pin::new(w).poll_shutdown(cx),
+            AsyncEncoder::Zstd(w) => Pin::new(w).poll_shutdown(cx),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{Error, InputArg, Operation, OutputArg};
+use either::Either;
+use std::io::{self, Write};
+
+/// Copy the entire contents of an input arg to an output arg, returning the
+/// number of bytes copied.
+///
+/// The input arg is opened as with [`InputArg::open()`], and the output arg
+/// is opened as with [`OutputArg::create()`].  The data is then copied with
+/// [`std::io::copy`] using the concrete reader & writer types rather than
+/// [`InputArgReader`][crate::InputArgReader] and
+/// [`OutputArgWriter`][crate::OutputArgWriter], which allows the standard
+/// library to transfer the data inside the kernel with `copy_file_range`,
+/// `sendfile`, or `splice` on Linux whenever the files, pipes, or sockets
+/// involved permit it.  On other platforms, or when zero-copy is not possible,
+/// the data is copied through a userspace buffer instead.
+///
+/// # Errors
+///
+/// Has the same error conditions as [`InputArg::open()`],
+/// [`OutputArg::create()`], and [`std::io::copy`].  Because a failed
+/// zero-copy transfer does not indicate which end was at fault, a failure
+/// during copying is reported as an [`Operation::Copy`] error on the output
+/// arg.
+///
+/// # Example
+///
+/// ```no_run
+/// use patharg::{InputArg, OutputArg};
+/// use std::env::args_os;
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut args = args_os().skip(1);
+///     let infile = args.next().map(InputArg::from_arg).unwrap_or_default();
+///     let outfile = args.next().map(OutputArg::from_arg).unwrap_or_default();
+///     let n = patharg::copy(&infile, &outfile)?;
+///     eprintln!("Copied {n} bytes");
+///     Ok(())
+/// }
+/// ```
+pub fn copy(input: &InputArg, output: &OutputArg) -> Result<u64, Error> {
+    let mut reader = input.open()?;
+    let mut writer = output.create()?;
+    match (&mut reader, &mut writer) {
+        (Either::Left(r), Either::Left(w)) => io::copy(r, w),
+        (Either::Left(r), Either::Right(w)) => io::copy(r, w),
+        (Either::Right(r), Either::Left(w)) => io::copy(r, w),
+        (Either::Right(r), Either::Right(w)) => io::copy(r, w),
+    }
+    .and_then(|n| writer.flush().map(|()| n))
+    .map_err(|e| Error::output(Operation::Copy, output, e))
+}
@@ -0,0 +1,189 @@
+use crate::{Error, InputArg, InputArgReader, Operation};
+use encoding_rs::{Encoding, UTF_8};
+use encoding_rs_io::{DecodeReaderBytes, DecodeReaderBytesBuilder};
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "encoding")))]
+impl InputArg {
+    /// Open the input arg for reading, decoding its contents to UTF-8.
+    ///
+    /// The input arg is opened as with [`InputArg::open()`].  If the input
+    /// begins with a UTF-8, UTF-16LE, or UTF-16BE byte order mark, the BOM is
+    /// stripped and the rest of the input is decoded using the encoding that
+    /// it indicates.  Otherwise, the input is decoded using `encoding`, or as
+    /// UTF-8 if `encoding` is `None`.  Encodings can be looked up by name with
+    /// [`Encoding::for_label()`].
+    ///
+    /// Malformed byte sequences in the input are replaced with U+FFFD
+    /// REPLACEMENT CHARACTER, so the data read from the returned reader is
+    /// always valid UTF-8.
+    ///
+    /// The returned reader implements [`std::io::BufRead`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::open()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::{Encoding, InputArg};
+    /// use std::env::args_os;
+    /// use std::io::{self, Read};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let infile = args_os().nth(1)
+    ///                           .map(InputArg::from_arg)
+    ///                           .unwrap_or_default();
+    ///     let mut f = infile.open_decoded(Encoding::for_label(b"windows-1252"))?;
+    ///     let mut s = String::new();
+    ///     f.read_to_string(&mut s)?;
+    ///     println!("Read {} characters from input", s.chars().count());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn open_decoded(
+        &self,
+        encoding: Option<&'static Encoding>,
+    ) -> Result<DecodedReader, Error> {
+        let reader = self.open()?;
+        Ok(DecodedReader::new(reader, encoding.unwrap_or(UTF_8)))
+    }
+
+    /// Read the entire contents of the input arg into a string, decoding it
+    /// to UTF-8.
+    ///
+    /// The input is decoded as described for [`InputArg::open_decoded()`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::open_decoded()`] and
+    /// [`std::io::Read::read_to_string`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::InputArg;
+    /// use std::env::args_os;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let infile = args_os().nth(1)
+    ///                           .map(InputArg::from_arg)
+    ///                           .unwrap_or_default();
+    ///     // Handles UTF-16 input from Windows tools as long as it has a BOM:
+    ///     let input = infile.read_to_string_decoded(None)?;
+    ///     println!("Read {} characters from input", input.chars().count());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn read_to_string_decoded(
+        &self,
+        encoding: Option<&'static Encoding>,
+    ) -> Result<String, Error> {
+        let mut s = String::new();
+        self.open_decoded(encoding)?
+            .read_to_string(&mut s)
+            .map_err(|e| Error::input(Operation::Read, self, e))?;
+        Ok(s)
+    }
+
+    /// Return an iterator over the lines of the input arg, decoding it to
+    /// UTF-8.
+    ///
+    /// The input is decoded as described for [`InputArg::open_decoded()`].
+    ///
+    /// The returned iterator yields instances of `std::io::Result<String>`,
+    /// where each individual item has the same error conditions as
+    /// [`std::io::BufRead::read_line()`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::open_decoded()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::{Encoding, InputArg};
+    /// use std::env::args_os;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let infile = args_os().nth(1)
+    ///                           .map(InputArg::from_arg)
+    ///                           .unwrap_or_default();
+    ///     for (i, r) in infile.lines_decoded(Encoding::for_label(b"utf-16le"))?.enumerate() {
+    ///         let line = r?;
+    ///         println!("Line {} is {} characters long.", i + 1, line.chars().count());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn lines_decoded(
+        &self,
+        encoding: Option<&'static Encoding>,
+    ) -> Result<DecodedLines, Error> {
+        Ok(self.open_decoded(encoding)?.lines())
+    }
+}
+
+/// The type of the readers returned by [`InputArg::open_decoded()`].
+///
+/// This type implements [`std::io::BufRead`], and the data read from it is
+/// always valid UTF-8.
+#[cfg_attr(docsrs, doc(cfg(feature = "encoding")))]
+pub struct DecodedReader {
+    inner: BufReader<DecodeReaderBytes<InputArgReader, Vec<u8>>>,
+    encoding: &'static Encoding,
+}
+
+impl DecodedReader {
+    fn new(reader: InputArgReader, encoding: &'static Encoding) -> DecodedReader {
+        let inner = DecodeReaderBytesBuilder::new()
+            .encoding(Some(encoding))
+            .bom_override(true)
+            .strip_bom(true)
+            .build(reader);
+        DecodedReader {
+            inner: BufReader::new(inner),
+            encoding,
+        }
+    }
+
+    /// Returns the encoding that the input is decoded from when it does not
+    /// begin with a byte order mark
+    pub fn fallback_encoding(&self) -> &'static Encoding {
+        self.encoding
+    }
+}
+
+impl fmt::Debug for DecodedReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecodedReader")
+            .field("fallback_encoding", &self.encoding)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Read for DecodedReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl BufRead for DecodedReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+    }
+}
+
+/// The type of the iterators returned by [`InputArg::lines_decoded()`].
+///
+/// This iterator yields instances of `std::io::Result<String>`.
+#[cfg_attr(docsrs, doc(cfg(feature = "encoding")))]
+pub type DecodedLines = io::Lines<DecodedReader>;
@@ -0,0 +1,195 @@
+use crate::{InputArg, LogArg, OutputArg};
+use std::fmt;
+use std::io;
+
+/// Error returned when an I/O operation on an [`InputArg`] or [`OutputArg`]
+/// fails.
+///
+/// An `Error` wraps the underlying [`std::io::Error`] together with the
+/// argument that was being operated on and the kind of operation that failed.
+/// It is displayed as, e.g., `failed to open foo.txt: No such file or
+/// directory (os error 2)`, with standard input & output displayed as
+/// `<stdin>` and `<stdout>`.  Errors that occurred at a specific line of
+/// input also include the line number, e.g., `failed to read foo.txt at line
+/// 42: stream did not contain valid UTF-8`.
+///
+/// `Error` can be converted into a [`std::io::Error`] (with the same
+/// [`kind()`][Error::kind] and a message that still includes the argument), so
+/// functions returning [`std::io::Result`] can use the `?` operator on the
+/// results of this crate's methods.
+#[derive(Debug)]
+pub struct Error {
+    op: Operation,
+    arg: Arg,
+    line: Option<usize>,
+    source: io::Error,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Arg {
+    Input(InputArg),
+    Output(OutputArg),
+    Log(LogArg),
+}
+
+impl Error {
+    pub(crate) fn input(op: Operation, arg: &InputArg, source: io::Error) -> Error {
+        Error {
+            op,
+            arg: Arg::Input(arg.clone()),
+            line: None,
+            source,
+        }
+    }
+
+    pub(crate) fn output(op: Operation, arg: &OutputArg, source: io::Error) -> Error {
+        Error {
+            op,
+            arg: Arg::Output(arg.clone()),
+            line: None,
+            source,
+        }
+    }
+
+    pub(crate) fn log(op: Operation, arg: &LogArg, source: io::Error) -> Error {
+        Error {
+            op,
+            arg: Arg::Log(arg.clone()),
+            line: None,
+            source,
+        }
+    }
+
+    /// Attach the 1-based number of the line at which the error occurred
+    pub(crate) fn at_line(mut self, line: usize) -> Error {
+        self.line = Some(line);
+        self
+    }
+
+    /// Returns the operation that failed
+    pub fn operation(&self) -> Operation {
+        self.op
+    }
+
+    /// If the error occurred while operating on an [`InputArg`], returns a
+    /// reference to it
+    pub fn input_arg(&self) -> Option<&InputArg> {
+        match &self.arg {
+            Arg::Input(arg) => Some(arg),
+            Arg::Output(_) | Arg::Log(_) => None,
+        }
+    }
+
+    /// If the error occurred while operating on an [`OutputArg`], returns a
+    /// reference to it
+    pub fn output_arg(&self) -> Option<&OutputArg> {
+        match &self.arg {
+            Arg::Output(arg) => Some(arg),
+            Arg::Input(_) | Arg::Log(_) => None,
+        }
+    }
+
+    /// If the error occurred while operating on a [`LogArg`], returns a
+    /// reference to it
+    pub fn log_arg(&self) -> Option<&LogArg> {
+        match &self.arg {
+            Arg::Log(arg) => Some(arg),
+            Arg::Input(_) | Arg::Output(_) => None,
+        }
+    }
+
+    /// If the error occurred while reading a specific line of an input arg
+    /// (as with [`InputArg::numbered_lines()`]), returns the 1-based number of
+    /// that line
+    pub fn line_number(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// Returns the [`std::io::ErrorKind`] of the underlying I/O error
+    pub fn kind(&self) -> io::ErrorKind {
+        self.source.kind()
+    }
+
+    /// Returns a reference to the underlying I/O error
+    pub fn io_error(&self) -> &io::Error {
+        &self.source
+    }
+
+    /// Consumes the error and returns the underlying I/O error
+    pub fn into_io_error(self) -> io::Error {
+        self.source
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to {} ", self.op.verb())?;
+        match &self.arg {
+            Arg::Input(arg) => write!(f, "{arg:#}")?,
+            Arg::Output(arg) => write!(f, "{arg:#}")?,
+            Arg::Log(arg) => write!(f, "{arg:#}")?,
+        }
+        if let Some(line) = self.line {
+            write!(f, " at line {line}")?;
+        }
+        write!(f, ": {}", self.source)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl From<Error> for io::Error {
+    /// Convert an [`Error`] into a [`std::io::Error`] with the same kind whose
+    /// message is the `Error`'s `Display` output
+    fn from(e: Error) -> io::Error {
+        io::Error::new(e.kind(), e)
+    }
+}
+
+/// The kinds of operations that can produce an [`Error`]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Operation {
+    /// Opening an input or output arg
+    Open,
+
+    /// Creating an output arg
+    Create,
+
+    /// Reading from an input arg
+    Read,
+
+    /// Writing to an output arg
+    Write,
+
+    /// Moving a temporary file into place at an output arg's path
+    Commit,
+
+    /// Making a backup copy of an existing file before replacing it
+    Backup,
+
+    /// Copying data from an input arg to an output arg
+    Copy,
+
+    /// Parsing structured data read from an input arg
+    Parse,
+}
+
+impl Operation {
+    fn verb(self) -> &'static str {
+        match self {
+            Operation::Open => "open",
+            Operation::Create => "create",
+            Operation::Read => "read",
+            Operation::Write => "write to",
+            Operation::Commit => "commit",
+            Operation::Backup => "back up",
+            Operation::Copy => "copy to",
+            Operation::Parse => "parse",
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::os::fd::{AsRawFd, BorrowedFd, FromRawFd, RawFd};
+use std::path::Path;
+
+/// An already-open file descriptor inherited by the process, as referred to
+/// by an `fd:N` or `/dev/fd/N` command-line argument.
+///
+/// This is the type stored in the `Fd` variants of [`InputArg`],
+/// [`OutputArg`], and [`LogArg`].  Opening such an arg duplicates the
+/// descriptor (as with `dup(2)`) and wraps the duplicate in a
+/// [`std::fs::File`], so the resulting readers & writers share the open file
+/// description with the original descriptor: reading and writing start at the
+/// descriptor's current offset and advance it, flags such as `O_APPEND` are
+/// honored, nothing is ever truncated, and pipes & sockets work the same as
+/// regular files.  The original descriptor is never closed.
+///
+/// An `FdArg` can only be constructed via [`FromRawFd::from_raw_fd()`] or by
+/// an [`ArgParser`] on which [`fd_args()`][ArgParser::fd_args] has been
+/// enabled, as the caller must vouch for the validity of the descriptor.
+///
+/// [`InputArg`]: crate::InputArg
+/// [`OutputArg`]: crate::OutputArg
+/// [`LogArg`]: crate::LogArg
+/// [`ArgParser`]: crate::ArgParser
+/// [ArgParser::fd_args]: crate::ArgParser::fd_args
+#[cfg_attr(docsrs, doc(cfg(unix)))]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct FdArg(RawFd);
+
+impl FdArg {
+    /// If `arg` is of the form `fd:N` or `/dev/fd/N`, where `N` is a
+    /// nonnegative decimal integer with no sign, return `N`
+    pub(crate) fn parse(arg: &Path) -> Option<RawFd> {
+        let arg = arg.to_str()?;
+        let digits = arg
+            .strip_prefix("fd:")
+            .or_else(|| arg.strip_prefix("/dev/fd/"))?;
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse::<RawFd>().ok()
+    }
+
+    /// Duplicate the descriptor, returning the duplicate as a `File`
+    pub(crate) fn try_clone_to_file(&self) -> io::Result<fs::File> {
+        // SAFETY: The contract of `from_raw_fd()` guarantees that the
+        // descriptor is open for as long as `self` exists.
+        #[allow(unsafe_code)]
+        let fd = unsafe { BorrowedFd::borrow_raw(self.0) };
+        fd.try_clone_to_owned().map(fs::File::from)
+    }
+}
+
+impl AsRawFd for FdArg {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0
+    }
+}
+
+#[allow(unsafe_code)]
+impl FromRawFd for FdArg {
+    /// Construct an `FdArg` referring to the file descriptor `fd`.
+    ///
+    /// The `FdArg` does not take ownership of `fd`; it only duplicates it
+    /// whenever an arg containing it is opened.
+    ///
+    /// # Safety
+    ///
+    /// `fd` must be an open file descriptor, and it must remain open — not
+    /// closed, and not replaced with `dup2(2)` or similar — for as long as the
+    /// returned `FdArg` or any copy of it is in use.  In practice, this means
+    /// that `fd` should be a descriptor that the process inherited from its
+    /// parent and that no other part of the program takes ownership of.
+    unsafe fn from_raw_fd(fd: RawFd) -> FdArg {
+        FdArg(fd)
+    }
+}
+
+impl fmt::Display for FdArg {
+    /// Displays the descriptor in the form `fd:N`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "fd:{}", self.0)
+    }
+}
@@ -0,0 +1,87 @@
+#[cfg(unix)]
+use crate::FdArg;
+use std::io;
+use std::path::Path;
+
+cfg_if::cfg_if! {
+    if #[cfg(unix)] {
+        use std::fs;
+        use std::os::fd::{AsFd, BorrowedFd};
+        use std::os::unix::fs::MetadataExt;
+    } else {
+        use same_file::Handle;
+    }
+}
+
+/// An identifier for a regular file that is the same for every path or
+/// descriptor referring to that file
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) struct FileId(#[cfg(unix)] (u64, u64), #[cfg(not(unix))] Handle);
+
+#[cfg(unix)]
+impl FileId {
+    /// Identify the file at `path` without opening it.  Returns `None` if
+    /// nothing exists at `path` or if it is not a regular file.
+    pub(crate) fn of_path(path: &Path) -> io::Result<Option<FileId>> {
+        match fs::metadata(path) {
+            Ok(md) => Ok(FileId::of_metadata(&md)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Identify the file that standard input refers to, if it is a regular
+    /// file
+    pub(crate) fn stdin() -> io::Result<Option<FileId>> {
+        FileId::of_descriptor(io::stdin().as_fd())
+    }
+
+    /// Identify the file that standard output refers to, if it is a regular
+    /// file
+    pub(crate) fn stdout() -> io::Result<Option<FileId>> {
+        FileId::of_descriptor(io::stdout().as_fd())
+    }
+
+    /// Identify the file that `fd` refers to, if it is a regular file
+    pub(crate) fn of_fd(fd: &FdArg) -> io::Result<Option<FileId>> {
+        Ok(FileId::of_metadata(&fd.try_clone_to_file()?.metadata()?))
+    }
+
+    fn of_descriptor(fd: BorrowedFd<'_>) -> io::Result<Option<FileId>> {
+        let fp = fs::File::from(fd.try_clone_to_owned()?);
+        Ok(FileId::of_metadata(&fp.metadata()?))
+    }
+
+    fn of_metadata(md: &fs::Metadata) -> Option<FileId> {
+        md.is_file().then(|| FileId((md.dev(), md.ino())))
+    }
+}
+
+#[cfg(not(unix))]
+impl FileId {
+    /// Identify the file at `path`.  Returns `None` if nothing exists at
+    /// `path` or if it is not a regular file.
+    pub(crate) fn of_path(path: &Path) -> io::Result<Option<FileId>> {
+        match Handle::from_path(path) {
+            Ok(h) => FileId::of_handle(h),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Identify the file that standard input refers to, if it is a regular
+    /// file
+    pub(crate) fn stdin() -> io::Result<Option<FileId>> {
+        FileId::of_handle(Handle::stdin()?)
+    }
+
+    /// Identify the file that standard output refers to, if it is a regular
+    /// file
+    pub(crate) fn stdout() -> io::Result<Option<FileId>> {
+        FileId::of_handle(Handle::stdout()?)
+    }
+
+    fn of_handle(h: Handle) -> io::Result<Option<FileId>> {
+        Ok(h.as_file().metadata()?.is_file().then(|| FileId(h)))
+    }
+}
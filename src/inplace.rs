@@ -0,0 +1,355 @@
+use crate::{AtomicOutputArgWriter, Error, InputArg, InputArgReader, Operation, OutputArg};
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A path argument for a file that is to be edited in place, or — if the
+/// argument is a single hyphen/dash — for filtering standard input to standard
+/// output.
+///
+/// This is useful for implementing `--in-place` options: the original file is
+/// read from, and the new contents are written to a temporary file in the same
+/// directory that only replaces the original once
+/// [`commit()`][InPlaceWriter::commit] is called, optionally after saving a
+/// backup copy of the original (like `sed -i.bak`).
+///
+/// # Example
+///
+/// ```no_run
+/// use patharg::InPlaceArg;
+/// use std::env::args_os;
+/// use std::io::{self, Write};
+///
+/// fn main() -> io::Result<()> {
+///     let file = args_os().nth(1)
+///                         .map(InPlaceArg::from_arg)
+///                         .unwrap_or_default();
+///     let content = file.read_to_string()?;
+///     let mut f = file.create_with_backup(".bak")?;
+///     f.write_all(content.to_uppercase().as_bytes())?;
+///     f.commit()?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum InPlaceArg {
+    /// Refers to standard input (for reading) and standard output (for
+    /// writing).
+    ///
+    /// This is the variant returned by `InPlaceArg::default()`.
+    #[default]
+    Stdio,
+
+    /// Refers to a file system path (stored in `.0`) that is both read from
+    /// and written to
+    Path(PathBuf),
+}
+
+impl InPlaceArg {
+    /// Construct an `InPlaceArg` from a string, usually one taken from
+    /// command-line arguments.  If the string equals `"-"` (i.e., it contains
+    /// only a single hyphen/dash), [`InPlaceArg::Stdio`] is returned;
+    /// otherwise, an [`InPlaceArg::Path`] is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use patharg::InPlaceArg;
+    /// use std::path::PathBuf;
+    ///
+    /// let p1 = InPlaceArg::from_arg("-");
+    /// assert_eq!(p1, InPlaceArg::Stdio);
+    ///
+    /// let p2 = InPlaceArg::from_arg("./-");
+    /// assert_eq!(p2, InPlaceArg::Path(PathBuf::from("./-")));
+    /// ```
+    pub fn from_arg<S: Into<PathBuf>>(arg: S) -> InPlaceArg {
+        let arg = arg.into();
+        if arg == Path::new("-") {
+            InPlaceArg::Stdio
+        } else {
+            InPlaceArg::Path(arg)
+        }
+    }
+
+    /// Returns true if the in-place arg is the `Stdio` variant of
+    /// `InPlaceArg`.
+    pub fn is_stdio(&self) -> bool {
+        self == &InPlaceArg::Stdio
+    }
+
+    /// Returns true if the in-place arg is the `Path` variant of
+    /// `InPlaceArg`.
+    pub fn is_path(&self) -> bool {
+        matches!(self, InPlaceArg::Path(_))
+    }
+
+    /// Retrieve a reference to the inner [`PathBuf`].  If the in-place arg is
+    /// the `Stdio` variant, this returns `None`.
+    pub fn path_ref(&self) -> Option<&PathBuf> {
+        match self {
+            InPlaceArg::Stdio => None,
+            InPlaceArg::Path(p) => Some(p),
+        }
+    }
+
+    /// Consume the in-place arg and return the inner [`PathBuf`].  If the
+    /// in-place arg is the `Stdio` variant, this returns `None`.
+    pub fn into_path(self) -> Option<PathBuf> {
+        match self {
+            InPlaceArg::Stdio => None,
+            InPlaceArg::Path(p) => Some(p),
+        }
+    }
+
+    /// Return the [`InputArg`] that the in-place arg reads from:
+    /// [`InputArg::Stdin`] for the `Stdio` variant, or the same path for the
+    /// `Path` variant
+    pub fn input(&self) -> InputArg {
+        match self {
+            InPlaceArg::Stdio => InputArg::Stdin,
+            InPlaceArg::Path(p) => InputArg::Path(p.clone()),
+        }
+    }
+
+    /// Return the [`OutputArg`] that the in-place arg writes to:
+    /// [`OutputArg::Stdout`] for the `Stdio` variant, or the same path for
+    /// the `Path` variant
+    pub fn output(&self) -> OutputArg {
+        match self {
+            InPlaceArg::Stdio => OutputArg::Stdout,
+            InPlaceArg::Path(p) => OutputArg::Path(p.clone()),
+        }
+    }
+
+    /// Open the in-place arg for reading.  This is the same as calling
+    /// [`InputArg::open()`] on [`input()`][InPlaceArg::input].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::open()`].
+    pub fn open(&self) -> Result<InputArgReader, Error> {
+        self.input().open()
+    }
+
+    /// Read the entire contents of the in-place arg into a bytes vector.
+    /// This is the same as calling [`InputArg::read()`] on
+    /// [`input()`][InPlaceArg::input].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::read()`].
+    pub fn read(&self) -> Result<Vec<u8>, Error> {
+        self.input().read()
+    }
+
+    /// Read the entire contents of the in-place arg into a string.  This is
+    /// the same as calling [`InputArg::read_to_string()`] on
+    /// [`input()`][InPlaceArg::input].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::read_to_string()`].
+    pub fn read_to_string(&self) -> Result<String, Error> {
+        self.input().read_to_string()
+    }
+
+    /// Open the in-place arg for writing its new contents.
+    ///
+    /// If the in-place arg is the `Stdio` variant, the returned writer writes
+    /// directly to stdout.  Otherwise, data is written to a temporary file in
+    /// the same directory as the path, which is given the same permissions as
+    /// the original file; calling [`commit()`][InPlaceWriter::commit] on the
+    /// writer then replaces the original file with the temporary file.  If
+    /// the writer is dropped without being committed, the original file is
+    /// left untouched.
+    ///
+    /// The returned writer implements [`std::io::Write`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`OutputArg::create_atomic()`] and
+    /// [`std::fs::metadata`].
+    pub fn create(&self) -> Result<InPlaceWriter, Error> {
+        self.create_inner(None)
+    }
+
+    /// Open the in-place arg for writing its new contents, saving a backup
+    /// of the original file when the new contents are committed.
+    ///
+    /// This is the same as [`InPlaceArg::create()`], except that, if the
+    /// in-place arg is a `Path` variant, committing the writer first copies
+    /// the original file to a path formed by appending `suffix` to the
+    /// original path (e.g., `foo.txt` becomes `foo.txt.bak` for a suffix of
+    /// `".bak"`), replacing any file already there.  For the `Stdio` variant,
+    /// the suffix is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InPlaceArg::create()`].
+    pub fn create_with_backup<S: AsRef<OsStr>>(&self, suffix: S) -> Result<InPlaceWriter, Error> {
+        self.create_inner(Some(suffix.as_ref()))
+    }
+
+    fn create_inner(&self, backup_suffix: Option<&OsStr>) -> Result<InPlaceWriter, Error> {
+        let output = self.output();
+        let inner = output.create_atomic()?;
+        let backup = match self {
+            InPlaceArg::Stdio => None,
+            InPlaceArg::Path(p) => backup_suffix.map(|suffix| {
+                let mut backup = p.clone().into_os_string();
+                backup.push(suffix);
+                (p.clone(), PathBuf::from(backup))
+            }),
+        };
+        Ok(InPlaceWriter { inner, backup })
+    }
+
+    /// Replace the contents of the in-place arg with the given bytes.
+    ///
+    /// This creates a writer with [`InPlaceArg::create()`], writes
+    /// `contents` to it, and commits it.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InPlaceArg::create()`],
+    /// [`std::io::Write::write_all`], and [`InPlaceWriter::commit()`].
+    pub fn write<C: AsRef<[u8]>>(&self, contents: C) -> Result<(), Error> {
+        let mut writer = self.create()?;
+        writer
+            .write_all(contents.as_ref())
+            .map_err(|e| Error::output(Operation::Write, &self.output(), e))?;
+        writer.commit()
+    }
+}
+
+impl fmt::Display for InPlaceArg {
+    /// Displays [`InPlaceArg::Stdio`] as `-` (a single hyphen/dash) or as
+    /// `<stdin/stdout>` if the `{:#}` format is used.  Always displays
+    /// [`InPlaceArg::Path`] using [`std::path::Path::display()`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            // IMPORTANT: The default Display of Stdio has to round-trip back
+            // to Stdio so that InPlaceArg will work properly when used with
+            // clap's `default_value_t`.
+            InPlaceArg::Stdio => {
+                if f.alternate() {
+                    write!(f, "<stdin/stdout>")
+                } else {
+                    write!(f, "-")
+                }
+            }
+            InPlaceArg::Path(p) => write!(f, "{}", p.display()),
+        }
+    }
+}
+
+impl<S: Into<PathBuf>> From<S> for InPlaceArg {
+    /// Convert a string to an [`InPlaceArg`] using [`InPlaceArg::from_arg()`].
+    fn from(s: S) -> InPlaceArg {
+        InPlaceArg::from_arg(s)
+    }
+}
+
+impl FromStr for InPlaceArg {
+    type Err = std::convert::Infallible;
+
+    /// Convert a string to an [`InPlaceArg`] using [`InPlaceArg::from_arg()`].
+    fn from_str(s: &str) -> Result<InPlaceArg, Self::Err> {
+        Ok(InPlaceArg::from_arg(s))
+    }
+}
+
+impl From<InPlaceArg> for OsString {
+    /// Convert an [`InPlaceArg`] back to an `OsString`: `InPlaceArg::Stdio`
+    /// becomes `"-"`, and `InPlaceArg::Path(p)` becomes `p.into()`.
+    fn from(arg: InPlaceArg) -> OsString {
+        match arg {
+            InPlaceArg::Stdio => OsString::from("-"),
+            InPlaceArg::Path(p) => p.into(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for InPlaceArg {
+    /// Serializes [`InPlaceArg::Stdio`] as `"-"` (a string containing a
+    /// single hyphen/dash).  Serializes [`InPlaceArg::Path`] as the inner
+    /// [`PathBuf`]; this will fail if the path is not valid UTF-8.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            InPlaceArg::Stdio => "-".serialize(serializer),
+            InPlaceArg::Path(p) => p.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for InPlaceArg {
+    /// Deserializes a [`PathBuf`] and converts it to an `InPlaceArg` with
+    /// [`InPlaceArg::from_arg()`].
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        PathBuf::deserialize(deserializer).map(InPlaceArg::from_arg)
+    }
+}
+
+/// The type of the writers returned by [`InPlaceArg::create()`] and
+/// [`InPlaceArg::create_with_backup()`].
+///
+/// This type implements [`std::io::Write`].
+#[derive(Debug)]
+pub struct InPlaceWriter {
+    inner: AtomicOutputArgWriter,
+    backup: Option<(PathBuf, PathBuf)>,
+}
+
+impl InPlaceWriter {
+    /// Finish writing and replace the original file with the new contents.
+    ///
+    /// If a backup was requested, the original file is first copied to the
+    /// backup path (unless the original file does not exist).  The written
+    /// data is then moved into place as described for
+    /// [`AtomicOutputArgWriter::commit()`].  For the `Stdio` variant, this
+    /// just flushes stdout.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`std::fs::copy`] and
+    /// [`AtomicOutputArgWriter::commit()`].  If an error occurs, the original
+    /// file is left untouched.
+    pub fn commit(self) -> Result<(), Error> {
+        if let Some((path, backup)) = self.backup {
+            match fs::copy(&path, backup) {
+                Ok(_) => (),
+                Err(e) if e.kind() == io::ErrorKind::NotFound && !path.exists() => (),
+                Err(e) => return Err(Error::output(Operation::Backup, &OutputArg::Path(path), e)),
+            }
+        }
+        self.inner.commit()
+    }
+}
+
+impl Write for InPlaceWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.inner.write_all(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,444 @@
+use crate::{Error, InputArg, InputArgReader, Lines, Operation};
+use std::io::{self, BufRead, Read};
+use std::vec;
+
+/// A sequence of input args that can be read from as a single stream, in the
+/// manner of `cat` or Python's `fileinput` module.
+///
+/// Most commands that take input files accept any number of them and process
+/// them in order, reading from standard input if none are given.  An
+/// `InputArgs` models such a list of arguments: it is constructed from an
+/// iterator of values that can be converted into [`InputArg`]s, and if the
+/// iterator is empty, it is treated as containing a single
+/// [`InputArg::Stdin`].
+///
+/// Input args are only opened when they are reached during reading, so an
+/// `InputArgs` can be constructed during argument processing without touching
+/// the file system.
+///
+/// # Example
+///
+/// ```no_run
+/// use patharg::InputArgs;
+/// use std::env::args_os;
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let inputs = InputArgs::new(args_os().skip(1));
+///     let mut lines = inputs.lines();
+///     while let Some(r) = lines.next() {
+///         let line = r?;
+///         if let Some(arg) = lines.current_arg() {
+///             println!("{arg:#}:{}: {line}", lines.line_number());
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct InputArgs {
+    args: Vec<InputArg>,
+    stdin_policy: StdinPolicy,
+}
+
+impl InputArgs {
+    /// Construct an `InputArgs` from an iterator of arguments, usually ones
+    /// taken from the command line.  Each argument is converted to an
+    /// [`InputArg`] using [`InputArg::from()`]; if there are no arguments, the
+    /// result consists of just [`InputArg::Stdin`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use patharg::{InputArg, InputArgs};
+    ///
+    /// let inputs = InputArgs::new(["foo.txt", "-"]);
+    /// assert_eq!(
+    ///     inputs.args(),
+    ///     [InputArg::from_arg("foo.txt"), InputArg::Stdin]
+    /// );
+    ///
+    /// let inputs = InputArgs::new(Vec::<String>::new());
+    /// assert_eq!(inputs.args(), [InputArg::Stdin]);
+    /// ```
+    pub fn new<I>(args: I) -> InputArgs
+    where
+        I: IntoIterator,
+        I::Item: Into<InputArg>,
+    {
+        args.into_iter().map(Into::into).collect()
+    }
+
+    /// Set what the readers & iterators returned by [`InputArgs::open()`] and
+    /// [`InputArgs::lines()`] do when they reach an occurrence of
+    /// [`InputArg::Stdin`] after standard input has already been read by an
+    /// earlier one.  The default is [`StdinPolicy::AllowReuse`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::{InputArgs, StdinPolicy};
+    /// use std::env::args_os;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut inputs = InputArgs::new(args_os().skip(1));
+    ///     inputs.stdin_policy(StdinPolicy::DenyReuse);
+    ///     // Fails upon reaching a second `-` argument:
+    ///     io::copy(&mut inputs.open(), &mut io::stdout().lock())?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn stdin_policy(&mut self, policy: StdinPolicy) -> &mut Self {
+        self.stdin_policy = policy;
+        self
+    }
+
+    /// Check that standard input occurs at most once among the input args.
+    ///
+    /// As standard input can only be read through once, a command invoked
+    /// with `-` given multiple times will only see input for the first
+    /// occurrence.  This method lets such invocations be rejected before any
+    /// input is read.
+    ///
+    /// # Errors
+    ///
+    /// If [`InputArg::Stdin`] occurs more than once, an [`Error`] of kind
+    /// [`std::io::ErrorKind::InvalidInput`] is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use patharg::InputArgs;
+    ///
+    /// assert!(InputArgs::new(["-", "foo.txt"]).validate().is_ok());
+    /// assert!(InputArgs::new(["-", "foo.txt", "-"]).validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.args.iter().filter(|arg| arg.is_stdin()).count() > 1 {
+            Err(Error::input(
+                Operation::Open,
+                &InputArg::Stdin,
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "standard input specified more than once",
+                ),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns the input args as a slice
+    pub fn args(&self) -> &[InputArg] {
+        &self.args
+    }
+
+    /// Consume the `InputArgs` and return the input args as a vector
+    pub fn into_args(self) -> Vec<InputArg> {
+        self.args
+    }
+
+    /// Return a reader over the concatenated contents of the input args.
+    ///
+    /// Each input arg is opened with [`InputArg::open()`] once the contents
+    /// of the previous one have been exhausted.  If an input arg cannot be
+    /// opened, the read that reached it fails with the resulting
+    /// [`Error`][crate::Error] (converted to a [`std::io::Error`]), and the
+    /// next read continues with the following input arg.  Repeated
+    /// occurrences of standard input are handled according to the configured
+    /// [`StdinPolicy`].
+    ///
+    /// The returned reader implements [`std::io::BufRead`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::InputArgs;
+    /// use std::env::args_os;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let inputs = InputArgs::new(args_os().skip(1));
+    ///     io::copy(&mut inputs.open(), &mut io::stdout().lock())?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn open(&self) -> InputArgsReader {
+        InputArgsReader {
+            pending: self.pending(),
+            current: None,
+        }
+    }
+
+    /// Return an iterator over the lines of each of the input args in turn.
+    ///
+    /// Unlike calling [`BufRead::lines()`] on the reader returned by
+    /// [`InputArgs::open()`], lines are never joined across input args: if
+    /// an input arg does not end with a newline, its final line is still
+    /// yielded separately from the first line of the next input arg.  While
+    /// iterating, the iterator's [`current_arg()`][InputArgsLines::current_arg]
+    /// and [`line_number()`][InputArgsLines::line_number] methods report where
+    /// the most recently yielded line came from.
+    ///
+    /// The returned iterator yields instances of `Result<String,
+    /// patharg::Error>`.  If an input arg cannot be opened or an error occurs
+    /// while reading from it, the error is yielded, and iteration continues
+    /// with the following input arg.  Repeated occurrences of standard input
+    /// are handled according to the configured [`StdinPolicy`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::InputArgs;
+    /// use std::env::args_os;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let inputs = InputArgs::new(args_os().skip(1));
+    ///     for r in inputs.lines() {
+    ///         let line = r?;
+    ///         println!("{}", line.to_uppercase());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn lines(&self) -> InputArgsLines {
+        InputArgsLines {
+            pending: self.pending(),
+            current: None,
+            line_number: 0,
+        }
+    }
+
+    fn pending(&self) -> PendingArgs {
+        PendingArgs {
+            args: self.args.clone().into_iter(),
+            stdin_policy: self.stdin_policy,
+            stdin_used: false,
+        }
+    }
+}
+
+impl Default for InputArgs {
+    /// Returns an `InputArgs` consisting of just [`InputArg::Stdin`]
+    fn default() -> InputArgs {
+        InputArgs {
+            args: vec![InputArg::Stdin],
+            stdin_policy: StdinPolicy::default(),
+        }
+    }
+}
+
+impl FromIterator<InputArg> for InputArgs {
+    /// Collect an iterator of [`InputArg`]s into an `InputArgs`.  If the
+    /// iterator is empty, the result consists of just [`InputArg::Stdin`].
+    fn from_iter<I: IntoIterator<Item = InputArg>>(iter: I) -> InputArgs {
+        let args = iter.into_iter().collect::<Vec<_>>();
+        if args.is_empty() {
+            InputArgs::default()
+        } else {
+            InputArgs {
+                args,
+                stdin_policy: StdinPolicy::default(),
+            }
+        }
+    }
+}
+
+impl IntoIterator for InputArgs {
+    type Item = InputArg;
+    type IntoIter = vec::IntoIter<InputArg>;
+
+    fn into_iter(self) -> vec::IntoIter<InputArg> {
+        self.args.into_iter()
+    }
+}
+
+/// Policies for what happens when an [`InputArgs`] reaches an occurrence of
+/// [`InputArg::Stdin`] after standard input has already been read, as set by
+/// [`InputArgs::stdin_policy()`]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum StdinPolicy {
+    /// Every occurrence of [`InputArg::Stdin`] reads from standard input,
+    /// picking up wherever the previous occurrence left off (usually at
+    /// end-of-file, so that later occurrences see no input).
+    ///
+    /// This is the default policy.
+    #[default]
+    AllowReuse,
+
+    /// Only the first occurrence of [`InputArg::Stdin`] reads from standard
+    /// input; reaching any later occurrence produces an [`Error`] of kind
+    /// [`std::io::ErrorKind::InvalidInput`].
+    DenyReuse,
+
+    /// Only the first occurrence of [`InputArg::Stdin`] reads from standard
+    /// input; later occurrences are treated as empty input without touching
+    /// standard input again.
+    EmptyOnReuse,
+}
+
+/// The input args that have yet to be opened by an [`InputArgsReader`] or
+/// [`InputArgsLines`]
+#[derive(Debug)]
+struct PendingArgs {
+    args: vec::IntoIter<InputArg>,
+    stdin_policy: StdinPolicy,
+    stdin_used: bool,
+}
+
+impl PendingArgs {
+    /// Open the next input arg, applying the stdin policy.  Returns `None`
+    /// once all input args have been exhausted.
+    fn open_next(&mut self) -> Option<Result<(InputArg, InputArgReader), Error>> {
+        loop {
+            let arg = self.args.next()?;
+            if arg.is_stdin() {
+                if self.stdin_used {
+                    match self.stdin_policy {
+                        StdinPolicy::AllowReuse => (),
+                        StdinPolicy::DenyReuse => {
+                            return Some(Err(Error::input(
+                                Operation::Open,
+                                &arg,
+                                io::Error::new(
+                                    io::ErrorKind::InvalidInput,
+                                    "standard input has already been read",
+                                ),
+                            )));
+                        }
+                        StdinPolicy::EmptyOnReuse => continue,
+                    }
+                }
+                self.stdin_used = true;
+            }
+            return Some(arg.open().map(|reader| (arg, reader)));
+        }
+    }
+}
+
+/// The type of the readers returned by [`InputArgs::open()`].
+///
+/// This type implements [`std::io::BufRead`].
+#[derive(Debug)]
+pub struct InputArgsReader {
+    pending: PendingArgs,
+    current: Option<(InputArg, InputArgReader)>,
+}
+
+impl InputArgsReader {
+    /// Returns the input arg that is currently being read from, or `None` if
+    /// no input arg is currently open
+    pub fn current_arg(&self) -> Option<&InputArg> {
+        self.current.as_ref().map(|(arg, _)| arg)
+    }
+
+    /// Ensure that `self.current` is an input arg with data available to
+    /// read, opening subsequent input args as necessary.  Returns `false` if
+    /// all input args have been exhausted.
+    fn prepare(&mut self) -> io::Result<bool> {
+        loop {
+            if let Some((arg, reader)) = &mut self.current {
+                match reader.fill_buf() {
+                    Ok([]) => self.current = None,
+                    Ok(_) => return Ok(true),
+                    Err(e) => return Err(Error::input(Operation::Read, arg, e).into()),
+                }
+            } else if let Some(r) = self.pending.open_next() {
+                self.current = Some(r?);
+            } else {
+                return Ok(false);
+            }
+        }
+    }
+}
+
+impl Read for InputArgsReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let mut available = self.fill_buf()?;
+        let n = available.read(buf)?;
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for InputArgsReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if !self.prepare()? {
+            return Ok(&[]);
+        }
+        match &mut self.current {
+            Some((arg, reader)) => reader
+                .fill_buf()
+                .map_err(|e| Error::input(Operation::Read, arg, e).into()),
+            None => Ok(&[]),
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if let Some((_, reader)) = &mut self.current {
+            reader.consume(amt);
+        }
+    }
+}
+
+/// The type of the iterators returned by [`InputArgs::lines()`].
+///
+/// This iterator yields instances of `Result<String, patharg::Error>`.
+#[derive(Debug)]
+pub struct InputArgsLines {
+    pending: PendingArgs,
+    current: Option<(InputArg, Lines)>,
+    line_number: usize,
+}
+
+impl InputArgsLines {
+    /// Returns the input arg that the most recently yielded line was read
+    /// from, or `None` if no line has been yielded from the current input arg
+    pub fn current_arg(&self) -> Option<&InputArg> {
+        self.current.as_ref().map(|(arg, _)| arg)
+    }
+
+    /// Returns the 1-based line number of the most recently yielded line
+    /// within its input arg, or 0 if no line has been yielded from the
+    /// current input arg
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+}
+
+impl Iterator for InputArgsLines {
+    type Item = Result<String, Error>;
+
+    fn next(&mut self) -> Option<Result<String, Error>> {
+        loop {
+            if let Some((arg, lines)) = &mut self.current {
+                match lines.next() {
+                    Some(Ok(line)) => {
+                        self.line_number += 1;
+                        return Some(Ok(line));
+                    }
+                    Some(Err(e)) => {
+                        let e = Error::input(Operation::Read, arg, e);
+                        self.current = None;
+                        self.line_number = 0;
+                        return Some(Err(e));
+                    }
+                    None => {
+                        self.current = None;
+                        self.line_number = 0;
+                    }
+                }
+            } else {
+                match self.pending.open_next()? {
+                    Ok((arg, reader)) => self.current = Some((arg, reader.lines())),
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,495 @@
+use crate::structured::invalid_data;
+use crate::{Error, InputArg, NumberedLines, Operation, OutputArg, OutputArgWriter};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::io::{self, BufWriter, Write};
+use std::marker::PhantomData;
+
+#[cfg(feature = "tokio")]
+use crate::{AsyncLines, AsyncOutputArgWriter};
+#[cfg(feature = "tokio")]
+use futures_sink::Sink;
+#[cfg(feature = "tokio")]
+use std::future::poll_fn;
+#[cfg(feature = "tokio")]
+use std::pin::Pin;
+#[cfg(feature = "tokio")]
+use std::task::{Context, Poll, ready};
+#[cfg(feature = "tokio")]
+use tokio::io::AsyncWrite;
+#[cfg(feature = "tokio")]
+use tokio_stream::Stream;
+
+/// Size above which [`AsyncJsonLinesWriter`] writes out its buffer before
+/// accepting more records
+#[cfg(feature = "tokio")]
+const ASYNC_BUFFER_SIZE: usize = 8192;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+impl InputArg {
+    /// Return an iterator that deserializes each line of the input arg as a
+    /// JSON value of type `T`, as in the [JSON Lines][jsonl] format.
+    ///
+    /// Lines that are empty or contain only whitespace are skipped.
+    ///
+    /// The returned iterator yields instances of `Result<T,
+    /// patharg::Error>`.  If a line cannot be read, is not valid JSON, or
+    /// does not match `T`, the error names the input arg and records the
+    /// line number, which can be retrieved with [`Error::line_number()`];
+    /// iteration can then continue with the next line.
+    ///
+    /// [jsonl]: https://jsonlines.org
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::open()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::InputArg;
+    /// use serde::Deserialize;
+    /// use std::env::args_os;
+    /// use std::io;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Event {
+    ///     level: String,
+    ///     message: String,
+    /// }
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let infile = args_os().nth(1)
+    ///                           .map(InputArg::from_arg)
+    ///                           .unwrap_or_default();
+    ///     for r in infile.json_lines::<Event>()? {
+    ///         let event = r?;
+    ///         if event.level == "error" {
+    ///             println!("{}", event.message);
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn json_lines<T: DeserializeOwned>(&self) -> Result<JsonLines<T>, Error> {
+        Ok(JsonLines {
+            inner: self.numbered_lines()?,
+            _record: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "json", feature = "tokio"))))]
+impl InputArg {
+    /// Return a stream that deserializes each line of the input arg as a JSON
+    /// value of type `T`, as in the [JSON Lines][jsonl] format.
+    ///
+    /// This is the asynchronous counterpart of [`InputArg::json_lines()`].
+    /// The returned stream yields instances of `Result<T, patharg::Error>`,
+    /// with errors recording the line at which they occurred.
+    ///
+    /// [jsonl]: https://jsonlines.org
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::async_open()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::InputArg;
+    /// use std::env::args_os;
+    /// use tokio_stream::StreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let infile = args_os().nth(1)
+    ///                           .map(InputArg::from_arg)
+    ///                           .unwrap_or_default();
+    ///     let mut stream = infile.async_json_lines::<serde_json::Value>().await?;
+    ///     while let Some(r) = stream.next().await {
+    ///         let value = r?;
+    ///         println!("{value}");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn async_json_lines<T: DeserializeOwned>(&self) -> Result<AsyncJsonLines<T>, Error> {
+        Ok(AsyncJsonLines {
+            arg: self.clone(),
+            inner: self.async_lines().await?,
+            line_number: 0,
+            _record: PhantomData,
+        })
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+impl OutputArg {
+    /// Open the output arg for writing records in the [JSON Lines][jsonl]
+    /// format.
+    ///
+    /// The output arg is opened as with [`OutputArg::create()`], and a
+    /// [`JsonLinesWriter`] is returned that writes each record as compact
+    /// JSON followed by a newline.
+    ///
+    /// [jsonl]: https://jsonlines.org
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`OutputArg::create()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::{InputArg, OutputArg};
+    /// use serde_json::Value;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut out = OutputArg::Stdout.create_json_lines()?;
+    ///     for r in InputArg::Stdin.json_lines::<Value>()? {
+    ///         let mut value = r?;
+    ///         value["seen"] = Value::Bool(true);
+    ///         out.write_record(&value)?;
+    ///     }
+    ///     out.flush()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn create_json_lines(&self) -> Result<JsonLinesWriter, Error> {
+        Ok(JsonLinesWriter {
+            arg: self.clone(),
+            inner: BufWriter::new(self.create()?),
+        })
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "json", feature = "tokio"))))]
+impl OutputArg {
+    /// Asynchronously open the output arg for writing records in the [JSON
+    /// Lines][jsonl] format.
+    ///
+    /// The output arg is opened as with [`OutputArg::async_create()`], and an
+    /// [`AsyncJsonLinesWriter`] is returned that writes each record as
+    /// compact JSON followed by a newline.
+    ///
+    /// [jsonl]: https://jsonlines.org
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`OutputArg::async_create()`].
+    pub async fn async_create_json_lines(&self) -> Result<AsyncJsonLinesWriter, Error> {
+        Ok(AsyncJsonLinesWriter {
+            arg: self.clone(),
+            inner: self.async_create().await?,
+            buf: Vec::new(),
+            written: 0,
+        })
+    }
+}
+
+/// An iterator over the JSON Lines records of an input arg, returned by
+/// [`InputArg::json_lines()`].
+///
+/// This iterator yields instances of `Result<T, patharg::Error>`.
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub struct JsonLines<T> {
+    inner: NumberedLines,
+    _record: PhantomData<fn() -> T>,
+}
+
+impl<T> JsonLines<T> {
+    /// Returns the input arg that the records are read from
+    pub fn input_arg(&self) -> &InputArg {
+        self.inner.input_arg()
+    }
+
+    /// Returns the 1-based line number of the most recently read line, or 0
+    /// if no line has been read yet
+    pub fn line_number(&self) -> usize {
+        self.inner.line_number()
+    }
+}
+
+impl<T> fmt::Debug for JsonLines<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonLines")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for JsonLines<T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Result<T, Error>> {
+        loop {
+            let (line_number, _, line) = match self.inner.next()? {
+                Ok(item) => item,
+                Err(e) => return Some(Err(e)),
+            };
+            if !line.trim().is_empty() {
+                return Some(parse_record(self.inner.input_arg(), line_number, &line));
+            }
+        }
+    }
+}
+
+/// An asynchronous stream over the JSON Lines records of an input arg,
+/// returned by [`InputArg::async_json_lines()`].
+///
+/// This stream yields instances of `Result<T, patharg::Error>`.
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "json", feature = "tokio"))))]
+pub struct AsyncJsonLines<T> {
+    arg: InputArg,
+    inner: AsyncLines,
+    line_number: usize,
+    _record: PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "tokio")]
+impl<T> AsyncJsonLines<T> {
+    /// Returns the input arg that the records are read from
+    pub fn input_arg(&self) -> &InputArg {
+        &self.arg
+    }
+
+    /// Returns the 1-based line number of the most recently read line, or 0
+    /// if no line has been read yet
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T> fmt::Debug for AsyncJsonLines<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncJsonLines")
+            .field("arg", &self.arg)
+            .field("line_number", &self.line_number)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T: DeserializeOwned> Stream for AsyncJsonLines<T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            let Some(r) = ready!(Pin::new(&mut this.inner).poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+            this.line_number += 1;
+            match r {
+                Ok(line) if line.trim().is_empty() => (),
+                Ok(line) => {
+                    return Poll::Ready(Some(parse_record(&this.arg, this.line_number, &line)));
+                }
+                Err(e) => {
+                    return Poll::Ready(Some(Err(
+                        Error::input(Operation::Read, &this.arg, e).at_line(this.line_number)
+                    )));
+                }
+            }
+        }
+    }
+}
+
+/// A writer of JSON Lines records to an output arg, returned by
+/// [`OutputArg::create_json_lines()`].
+///
+/// Records are buffered; call [`JsonLinesWriter::flush()`] when done
+/// writing, as any error that occurs while flushing on drop is ignored.
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug)]
+pub struct JsonLinesWriter {
+    arg: OutputArg,
+    inner: BufWriter<OutputArgWriter>,
+}
+
+impl JsonLinesWriter {
+    /// Returns the output arg that the records are written to
+    pub fn output_arg(&self) -> &OutputArg {
+        &self.arg
+    }
+
+    /// Serialize `record` as compact JSON and write it followed by a newline
+    ///
+    /// If serializing fails, nothing is written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Operation::Write`] error if serializing or writing fails.
+    pub fn write_record<T: ?Sized + Serialize>(&mut self, record: &T) -> Result<(), Error> {
+        serialize_record(record)
+            .and_then(|line| self.inner.write_all(&line))
+            .map_err(|e| Error::output(Operation::Write, &self.arg, e))
+    }
+
+    /// Flush any buffered records to the output arg
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Operation::Write`] error if flushing fails.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        self.inner
+            .flush()
+            .map_err(|e| Error::output(Operation::Write, &self.arg, e))
+    }
+}
+
+/// An asynchronous writer of JSON Lines records to an output arg, returned
+/// by [`OutputArg::async_create_json_lines()`].
+///
+/// Records can be written either with
+/// [`write_record()`][AsyncJsonLinesWriter::write_record] or through the
+/// writer's implementation of [`futures_sink::Sink`], which accepts any
+/// serializable record type.  Records are buffered; call
+/// [`flush()`][AsyncJsonLinesWriter::flush] or
+/// [`close()`][AsyncJsonLinesWriter::close] when done writing.
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "json", feature = "tokio"))))]
+#[derive(Debug)]
+pub struct AsyncJsonLinesWriter {
+    arg: OutputArg,
+    inner: AsyncOutputArgWriter,
+    buf: Vec<u8>,
+    written: usize,
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncJsonLinesWriter {
+    /// Returns the output arg that the records are written to
+    pub fn output_arg(&self) -> &OutputArg {
+        &self.arg
+    }
+
+    /// Serialize `record` as compact JSON and write it followed by a newline
+    ///
+    /// If serializing fails, nothing is written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Operation::Write`] error if serializing or writing fails.
+    #[allow(clippy::future_not_send)] // The Future is Send if T is Sync
+    pub async fn write_record<T: ?Sized + Serialize>(&mut self, record: &T) -> Result<(), Error> {
+        self.push_record(record)?;
+        poll_fn(|cx| self.poll_reserve(cx)).await
+    }
+
+    /// Flush any buffered records to the output arg
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Operation::Write`] error if flushing fails.
+    pub async fn flush(&mut self) -> Result<(), Error> {
+        poll_fn(|cx| self.poll_flush_all(cx)).await
+    }
+
+    /// Flush any buffered records and shut down the output arg's writer
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Operation::Write`] error if flushing or shutting down
+    /// fails.
+    pub async fn close(&mut self) -> Result<(), Error> {
+        poll_fn(|cx| self.poll_close_all(cx)).await
+    }
+
+    fn push_record<T: ?Sized + Serialize>(&mut self, record: &T) -> Result<(), Error> {
+        let line =
+            serialize_record(record).map_err(|e| Error::output(Operation::Write, &self.arg, e))?;
+        self.buf.extend_from_slice(&line);
+        Ok(())
+    }
+
+    /// Write out the buffer if it has grown past [`ASYNC_BUFFER_SIZE`]
+    fn poll_reserve(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        if self.buf.len() >= ASYNC_BUFFER_SIZE {
+            self.poll_write_buf(cx)
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn poll_write_buf(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        while self.written < self.buf.len() {
+            match ready!(Pin::new(&mut self.inner).poll_write(cx, &self.buf[self.written..])) {
+                Ok(0) => {
+                    return Poll::Ready(Err(Error::output(
+                        Operation::Write,
+                        &self.arg,
+                        io::ErrorKind::WriteZero.into(),
+                    )));
+                }
+                Ok(n) => self.written += n,
+                Err(e) => return Poll::Ready(Err(Error::output(Operation::Write, &self.arg, e))),
+            }
+        }
+        self.buf.clear();
+        self.written = 0;
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_flush_all(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        ready!(self.poll_write_buf(cx))?;
+        Pin::new(&mut self.inner)
+            .poll_flush(cx)
+            .map_err(|e| Error::output(Operation::Write, &self.arg, e))
+    }
+
+    fn poll_close_all(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        ready!(self.poll_flush_all(cx))?;
+        Pin::new(&mut self.inner)
+            .poll_shutdown(cx)
+            .map_err(|e| Error::output(Operation::Write, &self.arg, e))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T: Serialize> Sink<T> for AsyncJsonLinesWriter {
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        self.get_mut().poll_reserve(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Error> {
+        self.get_mut().push_record(&item)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        self.get_mut().poll_flush_all(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        self.get_mut().poll_close_all(cx)
+    }
+}
+
+/// Serialize `record` as compact JSON followed by a newline.  The record is
+/// serialized into a separate buffer so that a failure partway through does
+/// not leave a partial record in the output.
+fn serialize_record<T: ?Sized + Serialize>(record: &T) -> io::Result<Vec<u8>> {
+    let mut line = serde_json::to_vec(record)?;
+    line.push(b'\n');
+    Ok(line)
+}
+
+/// Deserialize `line` as a JSON value, attributing any error to the given
+/// input arg & line number
+fn parse_record<T: DeserializeOwned>(
+    arg: &InputArg,
+    line_number: usize,
+    line: &str,
+) -> Result<T, Error> {
+    serde_json::from_str(line)
+        .map_err(|e| Error::input(Operation::Parse, arg, invalid_data(e)).at_line(line_number))
+}
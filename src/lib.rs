@@ -27,18 +27,69 @@
 //! [`revchars`]: https://github.com/jwodder/patharg/tree/master/examples/revchars/
 //! [`tokio-revchars`]: https://github.com/jwodder/patharg/tree/master/examples/tokio-revchars/
 //!
+//! If you need a sentinel other than `-`, or want paths like `/dev/stdin` to
+//! be treated as the standard streams, use an [`ArgParser`] to convert
+//! arguments instead.
+//!
+//! For commands that take any number of input files, the [`InputArgs`] type
+//! wraps a list of arguments (defaulting to standard input when the list is
+//! empty) and lets you read from all of them in sequence as a single stream.
+//! For commands that edit files in place, the [`InPlaceArg`] type reads from a
+//! path and then atomically replaces it with new contents, or — given a hyphen
+//! — reads from standard input and writes to standard output.  For options
+//! like `--log-file`, the [`LogArg`] type can additionally refer to standard
+//! error.
+//!
+//! Errors
+//! ======
+//!
+//! The methods of `InputArg` and `OutputArg` that perform I/O return
+//! [`patharg::Error`][Error] on failure.  This type wraps the underlying
+//! [`std::io::Error`] along with the argument that was being operated on, so
+//! that error messages name the offending file (e.g., `failed to open foo.txt:
+//! No such file or directory (os error 2)`).  `patharg::Error` can be
+//! converted into `std::io::Error`, so the `?` operator can be used on these
+//! methods inside functions that return `std::io::Result`.
+//!
+//! By default, every `InputArg::Stdin` reads from standard input, so a command
+//! invoked as `tool - -` will see all of stdin the first time and nothing the
+//! second.  [`InputArgs::validate()`] can be used to reject such arguments up
+//! front, and [`InputArgs::stdin_policy()`] can make an [`InputArgs`] fail
+//! upon reaching a repeated stdin argument or treat it as empty instead.
+//!
 //! Features
 //! ========
 //!
 //! The `patharg` crate has the following optional features.  None of them are
 //! enabled by default.
 //!
-//! - `serde` — Enables serialization & deserialization of `InputArg` and
-//!   `OutputArg` values with [`serde`]
+//! - `compression` — Enables methods for transparently decompressing input
+//!   and compressing output with gzip, bzip2, xz, or Zstandard
+//!
+//! - `encoding` — Enables methods for reading input in UTF-16 or legacy
+//!   encodings, with byte order mark detection, using [`encoding_rs`]
+//!
+//! - `json` — Enables [`InputArg::read_json()`], [`OutputArg::write_json()`],
+//!   and [`OutputArg::write_json_pretty()`] for reading & writing JSON with
+//!   [`serde_json`], plus [`InputArg::json_lines()`] and
+//!   [`OutputArg::create_json_lines()`] for reading & writing JSON Lines.
+//!   Implies `serde`.
+//!
+//! - `mmap` — Enables [`InputArg::read_mapped()`] for memory-mapping input
+//!   files with [`memmap2`]
+//!
+//! - `serde` — Enables serialization & deserialization of `InputArg`,
+//!   `OutputArg`, `InPlaceArg`, and `LogArg` values with [`serde`]
 //!
 //! - `tokio` — Enables using `InputArg` and `OutputArg` values for
 //!   asynchronous I/O with [`tokio`]
 //!
+//! - `toml` — Enables [`InputArg::read_toml()`] for reading TOML with
+//!   [`toml`].  Implies `serde`.
+//!
+//! - `yaml` — Enables [`InputArg::read_yaml()`] for reading YAML with
+//!   [`serde_yaml_ng`].  Implies `serde`.
+//!
 //! Comparison with clio
 //! ====================
 //!
@@ -64,7 +115,79 @@
 //!
 //! [`clio`]: https://crates.io/crates/clio
 
+mod atomic;
+mod backup;
+mod copy;
+mod error;
+#[cfg(unix)]
+mod fd;
+mod fileid;
+mod inplace;
+mod inputs;
+mod lines;
+mod log;
+mod options;
+mod parser;
+mod spool;
+mod tee;
+pub use crate::atomic::AtomicOutputArgWriter;
+pub use crate::backup::{BackupPolicy, ParseBackupPolicyError};
+pub use crate::copy::copy;
+pub use crate::error::{Error, Operation};
+#[cfg(unix)]
+pub use crate::fd::FdArg;
+pub use crate::inplace::{InPlaceArg, InPlaceWriter};
+pub use crate::inputs::{InputArgs, InputArgsLines, InputArgsReader, StdinPolicy};
+#[cfg(feature = "tokio")]
+pub use crate::lines::AsyncByteLines;
+pub use crate::lines::{ByteLines, Line, LineTerminator, LinesWithTerminators, NumberedLines};
+#[cfg(feature = "tokio")]
+pub use crate::log::AsyncLogArgWriter;
+pub use crate::log::{LogArg, LogArgWriter, STDERR_ARG};
+pub use crate::options::OutputArgOptions;
+pub use crate::parser::ArgParser;
+pub use crate::spool::DEFAULT_SPOOL_THRESHOLD;
+pub use crate::tee::TeeWriter;
 use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "compression")] {
+        mod compression;
+        pub use crate::compression::{
+            CompressedWriter, Compression, DecompressedLines, DecompressedReader,
+        };
+        #[cfg(feature = "tokio")]
+        pub use crate::compression::AsyncCompressedWriter;
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "encoding")] {
+        mod encoding;
+        pub use crate::encoding::{DecodedLines, DecodedReader};
+        pub use encoding_rs::Encoding;
+    }
+}
+
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+mod structured;
+
+cfg_if! {
+    if #[cfg(feature = "json")] {
+        mod jsonl;
+        pub use crate::jsonl::{JsonLines, JsonLinesWriter};
+        #[cfg(feature = "tokio")]
+        pub use crate::jsonl::{AsyncJsonLines, AsyncJsonLinesWriter};
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "mmap")] {
+        mod mmap;
+        pub use crate::mmap::MappedInput;
+    }
+}
+use crate::fileid::FileId;
 use either::Either;
 use std::ffi::OsString;
 use std::fmt;
@@ -85,7 +208,7 @@ cfg_if! {
     if #[cfg(feature = "tokio")] {
         use tokio::io::{AsyncReadExt, AsyncWriteExt, AsyncBufReadExt};
         use tokio_util::either::Either as AsyncEither;
-        use tokio_stream::wrappers::LinesStream;
+        use tokio_stream::wrappers::{LinesStream, SplitStream};
     }
 }
 
@@ -100,6 +223,13 @@ pub enum InputArg {
 
     /// Refers to a file system path (stored in `.0`)
     Path(PathBuf),
+
+    /// Refers to an already-open file descriptor (stored in `.0`), as
+    /// produced by an [`ArgParser`] with [`fd_args()`][ArgParser::fd_args]
+    /// enabled
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    Fd(FdArg),
 }
 
 impl InputArg {
@@ -164,7 +294,7 @@ impl InputArg {
     }
 
     /// Retrieve a reference to the inner [`PathBuf`].  If the input arg is
-    /// the `Stdin` variant, this returns `None`.
+    /// the `Stdin` or `Fd` variant, this returns `None`.
     ///
     /// # Example
     ///
@@ -181,12 +311,14 @@ impl InputArg {
     pub fn path_ref(&self) -> Option<&PathBuf> {
         match self {
             InputArg::Stdin => None,
+            #[cfg(unix)]
+            InputArg::Fd(_) => None,
             InputArg::Path(p) => Some(p),
         }
     }
 
     /// Retrieve a mutable reference to the inner [`PathBuf`].  If the input
-    /// arg is the `Stdin` variant, this returns `None`.
+    /// arg is the `Stdin` or `Fd` variant, this returns `None`.
     ///
     /// # Example
     ///
@@ -203,12 +335,14 @@ impl InputArg {
     pub fn path_mut(&mut self) -> Option<&mut PathBuf> {
         match self {
             InputArg::Stdin => None,
+            #[cfg(unix)]
+            InputArg::Fd(_) => None,
             InputArg::Path(p) => Some(p),
         }
     }
 
     /// Consume the input arg and return the inner [`PathBuf`].  If the input
-    /// arg is the `Stdin` variant, this returns `None`.
+    /// arg is the `Stdin` or `Fd` variant, this returns `None`.
     ///
     /// # Example
     ///
@@ -225,6 +359,8 @@ impl InputArg {
     pub fn into_path(self) -> Option<PathBuf> {
         match self {
             InputArg::Stdin => None,
+            #[cfg(unix)]
+            InputArg::Fd(_) => None,
             InputArg::Path(p) => Some(p),
         }
     }
@@ -259,10 +395,17 @@ impl InputArg {
     ///     Ok(())
     /// }
     /// ```
-    pub fn open(&self) -> io::Result<InputArgReader> {
+    pub fn open(&self) -> Result<InputArgReader, Error> {
         Ok(match self {
             InputArg::Stdin => Either::Left(io::stdin().lock()),
-            InputArg::Path(p) => Either::Right(BufReader::new(fs::File::open(p)?)),
+            InputArg::Path(p) => Either::Right(BufReader::new(
+                fs::File::open(p).map_err(|e| Error::input(Operation::Open, self, e))?,
+            )),
+            #[cfg(unix)]
+            InputArg::Fd(fd) => Either::Right(BufReader::new(
+                fd.try_clone_to_file()
+                    .map_err(|e| Error::input(Operation::Open, self, e))?,
+            )),
         })
     }
 
@@ -293,15 +436,20 @@ impl InputArg {
     ///     Ok(())
     /// }
     /// ```
-    pub fn read(&self) -> io::Result<Vec<u8>> {
+    pub fn read(&self) -> Result<Vec<u8>, Error> {
         match self {
             InputArg::Stdin => {
                 let mut vec = Vec::new();
-                io::stdin().lock().read_to_end(&mut vec)?;
-                Ok(vec)
+                io::stdin().lock().read_to_end(&mut vec).map(|_| vec)
             }
             InputArg::Path(p) => fs::read(p),
+            #[cfg(unix)]
+            InputArg::Fd(fd) => fd.try_clone_to_file().and_then(|mut fp| {
+                let mut vec = Vec::new();
+                fp.read_to_end(&mut vec).map(|_| vec)
+            }),
         }
+        .map_err(|e| Error::input(Operation::Read, self, e))
     }
 
     /// Read the entire contents of the input arg into a string.
@@ -331,11 +479,46 @@ impl InputArg {
     ///     Ok(())
     /// }
     /// ```
-    pub fn read_to_string(&self) -> io::Result<String> {
+    pub fn read_to_string(&self) -> Result<String, Error> {
         match self {
             InputArg::Stdin => io::read_to_string(io::stdin().lock()),
             InputArg::Path(p) => fs::read_to_string(p),
+            #[cfg(unix)]
+            InputArg::Fd(fd) => fd.try_clone_to_file().and_then(io::read_to_string),
         }
+        .map_err(|e| Error::input(Operation::Read, self, e))
+    }
+
+    /// Read the entire contents of the input arg into a string, replacing
+    /// any invalid UTF-8 sequences with U+FFFD REPLACEMENT CHARACTER.
+    ///
+    /// This is like [`InputArg::read_to_string()`], except that it does not
+    /// fail on input that is not valid UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::read()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::InputArg;
+    /// use std::env::args_os;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let infile = args_os().nth(1)
+    ///                           .map(InputArg::from_arg)
+    ///                           .unwrap_or_default();
+    ///     let input = infile.read_to_string_lossy()?;
+    ///     println!("Read {} characters from input", input.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn read_to_string_lossy(&self) -> Result<String, Error> {
+        let bytes = self.read()?;
+        Ok(String::from_utf8(bytes)
+            .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned()))
     }
 
     /// Return an iterator over the lines of the input arg.
@@ -371,9 +554,239 @@ impl InputArg {
     ///     Ok(())
     /// }
     /// ```
-    pub fn lines(&self) -> io::Result<Lines> {
+    pub fn lines(&self) -> Result<Lines, Error> {
         Ok(self.open()?.lines())
     }
+
+    /// Return an iterator over the lines of the input arg together with their
+    /// line numbers & byte offsets.
+    ///
+    /// The returned iterator yields instances of `Result<(usize, u64, String),
+    /// patharg::Error>`, where each successful item consists of the 1-based
+    /// line number, the byte offset at which the line starts, and the line
+    /// itself without its terminator.  Unlike the errors from
+    /// [`InputArg::lines()`], errors from this iterator name the input arg
+    /// and line at which they occurred; see [`NumberedLines`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::open()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::InputArg;
+    /// use std::env::args_os;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let infile = args_os().nth(1)
+    ///                           .map(InputArg::from_arg)
+    ///                           .unwrap_or_default();
+    ///     for r in infile.numbered_lines()? {
+    ///         let (lineno, _, line) = r?;
+    ///         if line.trim_end() != line {
+    ///             eprintln!("{infile:#}:{lineno}: trailing whitespace");
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn numbered_lines(&self) -> Result<NumberedLines, Error> {
+        Ok(NumberedLines::new(self.clone(), self.open()?))
+    }
+
+    /// Return an iterator over the lines of the input arg that keeps each
+    /// line's terminator.
+    ///
+    /// This is like [`InputArg::lines()`], except that each line is yielded
+    /// as a [`Line`] that records whether it ended with `\n`, `\r\n`, or (for
+    /// the last line of the input) nothing at all.  Writing out each `Line`
+    /// unchanged reproduces the input byte-for-byte.
+    ///
+    /// The returned iterator yields instances of `std::io::Result<Line>`,
+    /// where each individual item has the same error conditions as
+    /// [`std::io::BufRead::read_line()`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::open()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::{InputArg, OutputArg};
+    /// use std::env::args_os;
+    /// use std::io::{self, Write};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let infile = args_os().nth(1)
+    ///                           .map(InputArg::from_arg)
+    ///                           .unwrap_or_default();
+    ///     let mut out = OutputArg::Stdout.create()?;
+    ///     for r in infile.lines_with_terminators()? {
+    ///         let line = r?;
+    ///         let reversed = line.content().chars().rev().collect::<String>();
+    ///         let term = line.terminator().map_or("", |t| t.as_str());
+    ///         write!(out, "{reversed}{term}")?;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn lines_with_terminators(&self) -> Result<LinesWithTerminators, Error> {
+        Ok(LinesWithTerminators::new(self.open()?))
+    }
+
+    /// Return an iterator over the lines of the input arg as byte vectors.
+    ///
+    /// This is like [`InputArg::lines()`], except that the lines are not
+    /// required to be valid UTF-8.  Each line has its terminating newline
+    /// (`\n`) or CR LF (`\r\n`) removed.
+    ///
+    /// The returned iterator yields instances of `std::io::Result<Vec<u8>>`,
+    /// where each individual item has the same error conditions as
+    /// [`std::io::BufRead::read_until()`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::open()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::InputArg;
+    /// use std::env::args_os;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let infile = args_os().nth(1)
+    ///                           .map(InputArg::from_arg)
+    ///                           .unwrap_or_default();
+    ///     for (i, r) in infile.byte_lines()?.enumerate() {
+    ///         let line = r?;
+    ///         println!("Line {} is {} bytes long.", i + 1, line.len());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn byte_lines(&self) -> Result<ByteLines, Error> {
+        Ok(ByteLines::new(self.open()?))
+    }
+
+    /// Return an iterator over the segments of the input arg separated by the
+    /// byte `delim`.
+    ///
+    /// Each segment is yielded as a byte vector with the delimiter removed.
+    /// Splitting on `b'\0'` reads NUL-delimited input, such as that produced
+    /// by `find -print0`.
+    ///
+    /// The returned iterator yields instances of `std::io::Result<Vec<u8>>`,
+    /// where each individual item has the same error conditions as
+    /// [`std::io::BufRead::read_until()`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::open()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::InputArg;
+    /// use std::env::args_os;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let infile = args_os().nth(1)
+    ///                           .map(InputArg::from_arg)
+    ///                           .unwrap_or_default();
+    ///     for r in infile.split(b'\0')? {
+    ///         let path = r?;
+    ///         println!("{}", String::from_utf8_lossy(&path));
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn split(&self, delim: u8) -> Result<Split, Error> {
+        Ok(self.open()?.split(delim))
+    }
+
+    /// Open the input arg for reading and seeking.
+    ///
+    /// If the input arg is a `Path` variant that refers to a seekable file,
+    /// the file is opened and returned directly.  Otherwise — if the input arg
+    /// is the `Stdin` variant, or if the path refers to something that cannot
+    /// seek, like a pipe — the entire input is read up front and spooled into
+    /// memory or, if it is larger than [`DEFAULT_SPOOL_THRESHOLD`] bytes, into
+    /// an anonymous temporary file.
+    ///
+    /// An `Fd` variant is returned directly only if its descriptor is
+    /// seekable and positioned at the start of its file; otherwise, the rest
+    /// of its input is spooled as above.  Either way, seeking to offset 0 of
+    /// the returned reader goes to where the descriptor's input begins, not
+    /// to some earlier point in the underlying file.
+    ///
+    /// The returned reader implements [`std::io::BufRead`] and
+    /// [`std::io::Seek`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`std::fs::File::open`],
+    /// [`std::io::Read::read_to_end`], and [`tempfile::tempfile`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::InputArg;
+    /// use std::env::args_os;
+    /// use std::io::{self, Read, Seek, SeekFrom};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let infile = args_os().nth(1)
+    ///                           .map(InputArg::from_arg)
+    ///                           .unwrap_or_default();
+    ///     let mut f = infile.open_seekable()?;
+    ///     f.seek(SeekFrom::End(-4))?;
+    ///     let mut trailer = [0; 4];
+    ///     f.read_exact(&mut trailer)?;
+    ///     println!("Last four bytes: {trailer:?}");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn open_seekable(&self) -> Result<SeekableInputArgReader, Error> {
+        self.open_seekable_with_threshold(DEFAULT_SPOOL_THRESHOLD)
+    }
+
+    /// Open the input arg for reading and seeking, spooling non-seekable input
+    /// to a temporary file if it is larger than `threshold` bytes.
+    ///
+    /// This is the same as [`InputArg::open_seekable()`], except that the
+    /// maximum amount of input held in memory can be configured.  A
+    /// `threshold` of zero causes all non-seekable input to be spooled to a
+    /// temporary file.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::open_seekable()`].
+    pub fn open_seekable_with_threshold(
+        &self,
+        threshold: usize,
+    ) -> Result<SeekableInputArgReader, Error> {
+        match self {
+            InputArg::Stdin => spool::spool(io::stdin().lock(), threshold),
+            InputArg::Path(p) => {
+                let fp = fs::File::open(p).map_err(|e| Error::input(Operation::Open, self, e))?;
+                spool::seekable_file(fp, threshold)
+            }
+            #[cfg(unix)]
+            InputArg::Fd(fd) => {
+                let fp = fd
+                    .try_clone_to_file()
+                    .map_err(|e| Error::input(Operation::Open, self, e))?;
+                spool::seekable_file(fp, threshold)
+            }
+        }
+        .map_err(|e| Error::input(Operation::Read, self, e))
+    }
 }
 
 #[cfg(feature = "tokio")]
@@ -410,10 +823,19 @@ impl InputArg {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn async_open(&self) -> io::Result<AsyncInputArgReader> {
+    pub async fn async_open(&self) -> Result<AsyncInputArgReader, Error> {
         Ok(match self {
             InputArg::Stdin => AsyncEither::Left(tokio::io::stdin()),
-            InputArg::Path(p) => AsyncEither::Right(tokio::fs::File::open(p).await?),
+            InputArg::Path(p) => AsyncEither::Right(
+                tokio::fs::File::open(p)
+                    .await
+                    .map_err(|e| Error::input(Operation::Open, self, e))?,
+            ),
+            #[cfg(unix)]
+            InputArg::Fd(fd) => AsyncEither::Right(tokio::fs::File::from_std(
+                fd.try_clone_to_file()
+                    .map_err(|e| Error::input(Operation::Open, self, e))?,
+            )),
         })
     }
 
@@ -445,15 +867,26 @@ impl InputArg {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn async_read(&self) -> io::Result<Vec<u8>> {
+    pub async fn async_read(&self) -> Result<Vec<u8>, Error> {
         match self {
             InputArg::Stdin => {
                 let mut vec = Vec::new();
-                tokio::io::stdin().read_to_end(&mut vec).await?;
-                Ok(vec)
+                tokio::io::stdin().read_to_end(&mut vec).await.map(|_| vec)
             }
             InputArg::Path(p) => tokio::fs::read(p).await,
+            #[cfg(unix)]
+            InputArg::Fd(fd) => match fd.try_clone_to_file() {
+                Ok(fp) => {
+                    let mut vec = Vec::new();
+                    tokio::fs::File::from_std(fp)
+                        .read_to_end(&mut vec)
+                        .await
+                        .map(|_| vec)
+                }
+                Err(e) => Err(e),
+            },
         }
+        .map_err(|e| Error::input(Operation::Read, self, e))
     }
 
     /// Asynchronously read the entire contents of the input arg into a string.
@@ -484,15 +917,26 @@ impl InputArg {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn async_read_to_string(&self) -> io::Result<String> {
+    pub async fn async_read_to_string(&self) -> Result<String, Error> {
         match self {
             InputArg::Stdin => {
                 let mut s = String::new();
-                tokio::io::stdin().read_to_string(&mut s).await?;
-                Ok(s)
+                tokio::io::stdin().read_to_string(&mut s).await.map(|_| s)
             }
             InputArg::Path(p) => tokio::fs::read_to_string(p).await,
+            #[cfg(unix)]
+            InputArg::Fd(fd) => match fd.try_clone_to_file() {
+                Ok(fp) => {
+                    let mut s = String::new();
+                    tokio::fs::File::from_std(fp)
+                        .read_to_string(&mut s)
+                        .await
+                        .map(|_| s)
+                }
+                Err(e) => Err(e),
+            },
         }
+        .map_err(|e| Error::input(Operation::Read, self, e))
     }
 
     /// Return a stream over the lines of the input arg.
@@ -532,11 +976,69 @@ impl InputArg {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn async_lines(&self) -> io::Result<AsyncLines> {
+    pub async fn async_lines(&self) -> Result<AsyncLines, Error> {
         Ok(LinesStream::new(
             tokio::io::BufReader::new(self.async_open().await?).lines(),
         ))
     }
+
+    /// Return a stream over the lines of the input arg as byte vectors.
+    ///
+    /// This is like [`InputArg::async_lines()`], except that the lines are
+    /// not required to be valid UTF-8.  Each line has its terminating newline
+    /// (`\n`) or CR LF (`\r\n`) removed.
+    ///
+    /// The returned stream yields instances of `std::io::Result<Vec<u8>>`,
+    /// where each individual item has the same error conditions as
+    /// [`tokio::io::AsyncBufReadExt::read_until()`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::async_open()`].
+    pub async fn async_byte_lines(&self) -> Result<AsyncByteLines, Error> {
+        Ok(AsyncByteLines::new(tokio::io::BufReader::new(
+            self.async_open().await?,
+        )))
+    }
+
+    /// Return a stream over the segments of the input arg separated by the
+    /// byte `delim`.
+    ///
+    /// Each segment is yielded as a byte vector with the delimiter removed.
+    ///
+    /// The returned stream yields instances of `std::io::Result<Vec<u8>>`,
+    /// where each individual item has the same error conditions as
+    /// [`tokio::io::AsyncBufReadExt::read_until()`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::async_open()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::InputArg;
+    /// use std::env::args_os;
+    /// use tokio_stream::StreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let infile = args_os().nth(1)
+    ///                           .map(InputArg::from_arg)
+    ///                           .unwrap_or_default();
+    ///     let mut stream = infile.async_split(b'\0').await?;
+    ///     while let Some(r) = stream.next().await {
+    ///         let path = r?;
+    ///         println!("{}", String::from_utf8_lossy(&path));
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn async_split(&self, delim: u8) -> Result<AsyncSplit, Error> {
+        Ok(SplitStream::new(
+            tokio::io::BufReader::new(self.async_open().await?).split(delim),
+        ))
+    }
 }
 
 impl fmt::Display for InputArg {
@@ -556,6 +1058,8 @@ impl fmt::Display for InputArg {
                 }
             }
             InputArg::Path(p) => write!(f, "{}", p.display()),
+            #[cfg(unix)]
+            InputArg::Fd(fd) => write!(f, "{fd}"),
         }
     }
 }
@@ -583,6 +1087,8 @@ impl From<InputArg> for OsString {
         match arg {
             InputArg::Stdin => OsString::from("-"),
             InputArg::Path(p) => p.into(),
+            #[cfg(unix)]
+            InputArg::Fd(fd) => fd.to_string().into(),
         }
     }
 }
@@ -593,10 +1099,17 @@ impl Serialize for InputArg {
     /// Serializes [`InputArg::Stdin`] as `"-"` (a string containing a single
     /// hyphen/dash).  Serializes [`InputArg::Path`] as the inner [`PathBuf`];
     /// this will fail if the path is not valid UTF-8.
+    ///
+    /// On Unix, serializes the `Fd` variant as `"fd:N"`.  This does not
+    /// round-trip: deserializing the result yields a `Path` variant, as
+    /// deserialization cannot vouch for the validity of a descriptor (see
+    /// [`FdArg`]).
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         match self {
             InputArg::Stdin => "-".serialize(serializer),
             InputArg::Path(p) => p.serialize(serializer),
+            #[cfg(unix)]
+            InputArg::Fd(fd) => serializer.collect_str(fd),
         }
     }
 }
@@ -606,6 +1119,9 @@ impl Serialize for InputArg {
 impl<'de> Deserialize<'de> for InputArg {
     /// Deserializes a [`PathBuf`] and converts it to an `InputArg` with
     /// [`InputArg::from_arg()`].
+    ///
+    /// A `Fd` variant is never produced, so an `"fd:N"` string (as produced
+    /// by serializing an `Fd` variant) is deserialized as a `Path` variant.
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
@@ -626,6 +1142,13 @@ pub enum OutputArg {
 
     /// Refers to a file system path (stored in `.0`)
     Path(PathBuf),
+
+    /// Refers to an already-open file descriptor (stored in `.0`), as
+    /// produced by an [`ArgParser`] with [`fd_args()`][ArgParser::fd_args]
+    /// enabled
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    Fd(FdArg),
 }
 
 impl OutputArg {
@@ -690,7 +1213,7 @@ impl OutputArg {
     }
 
     /// Retrieve a reference to the inner [`PathBuf`].  If the output arg is
-    /// the `Stdout` variant, this returns `None`.
+    /// the `Stdout` or `Fd` variant, this returns `None`.
     ///
     /// # Example
     ///
@@ -707,12 +1230,14 @@ impl OutputArg {
     pub fn path_ref(&self) -> Option<&PathBuf> {
         match self {
             OutputArg::Stdout => None,
+            #[cfg(unix)]
+            OutputArg::Fd(_) => None,
             OutputArg::Path(p) => Some(p),
         }
     }
 
     /// Retrieve a mutable reference to the inner [`PathBuf`].  If the output
-    /// arg is the `Stdout` variant, this returns `None`.
+    /// arg is the `Stdout` or `Fd` variant, this returns `None`.
     ///
     /// # Example
     ///
@@ -729,12 +1254,14 @@ impl OutputArg {
     pub fn path_mut(&mut self) -> Option<&mut PathBuf> {
         match self {
             OutputArg::Stdout => None,
+            #[cfg(unix)]
+            OutputArg::Fd(_) => None,
             OutputArg::Path(p) => Some(p),
         }
     }
 
     /// Consume the output arg and return the inner [`PathBuf`].  If the output
-    /// arg is the `Stdout` variant, this returns `None`.
+    /// arg is the `Stdout` or `Fd` variant, this returns `None`.
     ///
     /// # Example
     ///
@@ -751,44 +1278,132 @@ impl OutputArg {
     pub fn into_path(self) -> Option<PathBuf> {
         match self {
             OutputArg::Stdout => None,
+            #[cfg(unix)]
+            OutputArg::Fd(_) => None,
             OutputArg::Path(p) => Some(p),
         }
     }
 
-    /// Open the output arg for writing.
+    /// Returns true if the output arg and the given input arg refer to the
+    /// same regular file, so that opening the output arg for writing would
+    /// clobber the input before it is read.
     ///
-    /// If the output arg is the `Stdout` variant, this returns a locked
-    /// reference to stdout.  Otherwise, if the output arg is a `Path` variant,
-    /// the given path is opened for writing; if the path does not exist, it is
-    /// created.
+    /// The two args are compared by device & inode (or the platform
+    /// equivalent), so different paths to the same file (e.g., via hard links
+    /// or symlinks) are detected, as is standard input or output being
+    /// redirected to or from a file that the other arg names.  If either arg
+    /// is a path that does not exist, or if either refers to something other
+    /// than a regular file (such as a terminal or pipe), this returns false.
     ///
-    /// The returned writer implements [`std::io::Write`].
+    /// On Unix, paths are examined with [`std::fs::metadata()`] and the
+    /// standard streams & file descriptors with `fstat(2)`, so nothing is
+    /// opened; in particular, this does not block on FIFOs or require
+    /// permission to read or write the files.
     ///
     /// # Errors
     ///
-    /// Has the same error conditions as [`std::fs::File::create`].
+    /// Returns an error if either arg exists but could not be examined.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::{InputArg, OutputArg};
+    ///
+    /// let infile = InputArg::from_arg("foo.txt");
+    /// let outfile = OutputArg::from_arg("./foo.txt");
+    /// assert!(outfile.conflicts_with(&infile).unwrap());
+    /// ```
+    pub fn conflicts_with(&self, input: &InputArg) -> Result<bool, Error> {
+        let Some(outid) = (match self {
+            OutputArg::Stdout => FileId::stdout(),
+            OutputArg::Path(p) => FileId::of_path(p),
+            #[cfg(unix)]
+            OutputArg::Fd(fd) => FileId::of_fd(fd),
+        })
+        .map_err(|e| Error::output(Operation::Open, self, e))?
+        else {
+            return Ok(false);
+        };
+        let Some(inid) = (match input {
+            InputArg::Stdin => FileId::stdin(),
+            InputArg::Path(p) => FileId::of_path(p),
+            #[cfg(unix)]
+            InputArg::Fd(fd) => FileId::of_fd(fd),
+        })
+        .map_err(|e| Error::input(Operation::Open, input, e))?
+        else {
+            return Ok(false);
+        };
+        Ok(outid == inid)
+    }
+
+    /// Return a new [`OutputArgOptions`] for configuring how to open an
+    /// output arg, analogous to [`std::fs::File::options()`].
+    ///
+    /// The returned options are initially set up to behave the same as
+    /// [`OutputArg::create()`].
     ///
     /// # Example
     ///
     /// ```no_run
     /// use patharg::OutputArg;
-    /// use std::env::args_os;
     /// use std::io::{self, Write};
     ///
     /// fn main() -> io::Result<()> {
-    ///     let outfile = args_os().nth(1)
-    ///                            .map(OutputArg::from_arg)
-    ///                            .unwrap_or_default();
-    ///     let mut f = outfile.create()?;
-    ///     // The "{}" is replaced by either the output filepath or a hyphen.
-    ///     write!(&mut f, "I am writing to {}.", outfile)?;
+    ///     let outfile = OutputArg::from_arg("secret.txt");
+    ///     let mut opts = OutputArg::options();
+    ///     #[cfg(unix)]
+    ///     opts.mode(0o600);
+    ///     let mut f = opts.open(&outfile)?;
+    ///     writeln!(&mut f, "For your eyes only.")?;
     ///     Ok(())
     /// }
     /// ```
-    pub fn create(&self) -> io::Result<OutputArgWriter> {
-        Ok(match self {
+    pub fn options() -> OutputArgOptions {
+        OutputArgOptions::new()
+    }
+
+    /// Open the output arg for writing.
+    ///
+    /// If the output arg is the `Stdout` variant, this returns a locked
+    /// reference to stdout.  Otherwise, if the output arg is a `Path` variant,
+    /// the given path is opened for writing; if the path does not exist, it is
+    /// created.
+    ///
+    /// The returned writer implements [`std::io::Write`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`std::fs::File::create`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::OutputArg;
+    /// use std::env::args_os;
+    /// use std::io::{self, Write};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let outfile = args_os().nth(1)
+    ///                            .map(OutputArg::from_arg)
+    ///                            .unwrap_or_default();
+    ///     let mut f = outfile.create()?;
+    ///     // The "{}" is replaced by either the output filepath or a hyphen.
+    ///     write!(&mut f, "I am writing to {}.", outfile)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn create(&self) -> Result<OutputArgWriter, Error> {
+        Ok(match self {
             OutputArg::Stdout => Either::Left(io::stdout().lock()),
-            OutputArg::Path(p) => Either::Right(fs::File::create(p)?),
+            OutputArg::Path(p) => Either::Right(
+                fs::File::create(p).map_err(|e| Error::output(Operation::Create, self, e))?,
+            ),
+            #[cfg(unix)]
+            OutputArg::Fd(fd) => Either::Right(
+                fd.try_clone_to_file()
+                    .map_err(|e| Error::output(Operation::Create, self, e))?,
+            ),
         })
     }
 
@@ -819,10 +1434,237 @@ impl OutputArg {
     ///     Ok(())
     /// }
     /// ```
-    pub fn write<C: AsRef<[u8]>>(&self, contents: C) -> io::Result<()> {
+    pub fn write<C: AsRef<[u8]>>(&self, contents: C) -> Result<(), Error> {
         match self {
             OutputArg::Stdout => io::stdout().lock().write_all(contents.as_ref()),
             OutputArg::Path(p) => fs::write(p, contents),
+            #[cfg(unix)]
+            OutputArg::Fd(fd) => fd
+                .try_clone_to_file()
+                .and_then(|mut fp| fp.write_all(contents.as_ref())),
+        }
+        .map_err(|e| Error::output(Operation::Write, self, e))
+    }
+
+    /// Open the output arg for appending.
+    ///
+    /// If the output arg is the `Stdout` variant, this returns a locked
+    /// reference to stdout.  Otherwise, if the output arg is a `Path` variant,
+    /// the given path is opened for writing in append mode, so that any data
+    /// written is added to the end of the file's existing contents; if the
+    /// path does not exist, it is created.
+    ///
+    /// The returned writer implements [`std::io::Write`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`std::fs::OpenOptions::open`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::OutputArg;
+    /// use std::env::args_os;
+    /// use std::io::{self, Write};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let logfile = args_os().nth(1)
+    ///                            .map(OutputArg::from_arg)
+    ///                            .unwrap_or_default();
+    ///     let mut f = logfile.append()?;
+    ///     writeln!(&mut f, "Another line for the log.")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn append(&self) -> Result<OutputArgWriter, Error> {
+        OutputArg::options().append(true).open(self)
+    }
+
+    /// Append a slice to the end of the contents of the output arg.
+    ///
+    /// If the output arg is the `Stdout` variant, the given data is written to
+    /// stdout.  Otherwise, if the output arg is a `Path` variant, the given
+    /// data is appended to the contents of the given path; if the path does
+    /// not exist, it is created first.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`std::io::Write::write_all`] and
+    /// [`std::fs::OpenOptions::open`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::OutputArg;
+    /// use std::env::args_os;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let logfile = args_os().nth(1)
+    ///                            .map(OutputArg::from_arg)
+    ///                            .unwrap_or_default();
+    ///     logfile.write_append("Another line for the log.\n")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn write_append<C: AsRef<[u8]>>(&self, contents: C) -> Result<(), Error> {
+        self.append()?
+            .write_all(contents.as_ref())
+            .map_err(|e| Error::output(Operation::Write, self, e))
+    }
+
+    /// Open the output arg for writing, failing if the output path already
+    /// exists.
+    ///
+    /// If the output arg is the `Stdout` variant, this returns a locked
+    /// reference to stdout.  Otherwise, if the output arg is a `Path` variant,
+    /// the given path is created and opened for writing; if the path already
+    /// exists, an error is returned and the existing file is left untouched.
+    ///
+    /// The returned writer implements [`std::io::Write`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`std::fs::OpenOptions::open`] with
+    /// `create_new` set.  If the path already exists, the error will have a
+    /// kind of [`std::io::ErrorKind::AlreadyExists`], and its message will
+    /// name the offending path.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::OutputArg;
+    /// use std::env::args_os;
+    /// use std::io::{self, Write};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let outfile = args_os().nth(1)
+    ///                            .map(OutputArg::from_arg)
+    ///                            .unwrap_or_default();
+    ///     let mut f = outfile.create_new()?;
+    ///     writeln!(&mut f, "This file did not exist before.")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn create_new(&self) -> Result<OutputArgWriter, Error> {
+        OutputArg::options().create_new(true).open(self)
+    }
+
+    /// Open the output arg for writing, either overwriting or refusing to
+    /// overwrite an existing file depending on the value of `clobber`.
+    ///
+    /// If `clobber` is true, this is the same as [`OutputArg::create()`];
+    /// otherwise, this is the same as [`OutputArg::create_new()`].  This is
+    /// useful for implementing a `--force` option that overrides a program's
+    /// default no-clobber behavior.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`OutputArg::create()`] or
+    /// [`OutputArg::create_new()`], as appropriate.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::OutputArg;
+    /// use std::env::args_os;
+    /// use std::io::{self, Write};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let force = args_os().any(|arg| arg == "--force");
+    ///     let outfile = OutputArg::from_arg("output.txt");
+    ///     let mut f = outfile.create_with_clobber(force)?;
+    ///     writeln!(&mut f, "Output goes here.")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn create_with_clobber(&self, clobber: bool) -> Result<OutputArgWriter, Error> {
+        if clobber {
+            self.create()
+        } else {
+            self.create_new()
+        }
+    }
+
+    /// Open the output arg for writing, first backing up any existing file at
+    /// the output path according to `policy`.
+    ///
+    /// If the output arg is the `Stdout` variant, this returns a locked
+    /// reference to stdout.  Otherwise, if the output arg is a `Path` variant
+    /// and the path exists, it is renamed to a backup path chosen by the
+    /// given [`BackupPolicy`] (e.g., `foo.txt~` or `foo.txt.~1~`), and then a
+    /// new file is created at the path.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`OutputArg::create()`] and
+    /// [`std::fs::rename`].  If the new file cannot be created after the
+    /// backup was made, the backup is renamed back to the output path before
+    /// the error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::{BackupPolicy, OutputArg};
+    /// use std::io::{self, Write};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let outfile = OutputArg::from_arg("output.txt");
+    ///     let mut f = outfile.create_with_backup(BackupPolicy::Numbered)?;
+    ///     writeln!(&mut f, "The old output is still around.")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn create_with_backup(&self, policy: BackupPolicy) -> Result<OutputArgWriter, Error> {
+        OutputArg::options()
+            .backup(policy)
+            .open_with(self, Operation::Create)
+    }
+
+    /// Open the output arg for writing in such a way that the target path is
+    /// only replaced once writing has finished successfully.
+    ///
+    /// If the output arg is the `Stdout` variant, this returns a writer that
+    /// writes directly to a locked reference to stdout.  Otherwise, if the
+    /// output arg is a `Path` variant, a temporary file is created in the same
+    /// directory as the given path, and data is written to that.  Calling
+    /// [`AtomicOutputArgWriter::commit()`] then gives the temporary file the
+    /// permissions of any file already at the given path and renames it over
+    /// that path; if the writer is instead dropped without being
+    /// committed, the temporary file is deleted, and any file already at the
+    /// given path is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`tempfile::Builder::tempfile_in`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::OutputArg;
+    /// use std::env::args_os;
+    /// use std::io::{self, Write};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let outfile = args_os().nth(1)
+    ///                            .map(OutputArg::from_arg)
+    ///                            .unwrap_or_default();
+    ///     let mut f = outfile.create_atomic()?;
+    ///     writeln!(&mut f, "If this program crashes now, the old file survives.")?;
+    ///     f.commit()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn create_atomic(&self) -> Result<AtomicOutputArgWriter, Error> {
+        match self {
+            OutputArg::Stdout => Ok(AtomicOutputArgWriter::stdout()),
+            OutputArg::Path(p) => AtomicOutputArgWriter::create(p)
+                .map_err(|e| Error::output(Operation::Create, self, e)),
+            #[cfg(unix)]
+            OutputArg::Fd(fd) => fd
+                .try_clone_to_file()
+                .map(|fp| AtomicOutputArgWriter::file(fp, *fd))
+                .map_err(|e| Error::output(Operation::Create, self, e)),
         }
     }
 }
@@ -861,13 +1703,197 @@ impl OutputArg {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn async_create(&self) -> io::Result<AsyncOutputArgWriter> {
+    pub async fn async_create(&self) -> Result<AsyncOutputArgWriter, Error> {
         Ok(match self {
             OutputArg::Stdout => AsyncEither::Left(tokio::io::stdout()),
-            OutputArg::Path(p) => AsyncEither::Right(tokio::fs::File::create(p).await?),
+            OutputArg::Path(p) => AsyncEither::Right(
+                tokio::fs::File::create(p)
+                    .await
+                    .map_err(|e| Error::output(Operation::Create, self, e))?,
+            ),
+            #[cfg(unix)]
+            OutputArg::Fd(fd) => AsyncEither::Right(tokio::fs::File::from_std(
+                fd.try_clone_to_file()
+                    .map_err(|e| Error::output(Operation::Create, self, e))?,
+            )),
         })
     }
 
+    /// Asynchronously open the output arg for appending.
+    ///
+    /// If the output arg is the `Stdout` variant, this returns a reference to
+    /// stdout.  Otherwise, if the output arg is a `Path` variant, the given
+    /// path is opened for writing in append mode, so that any data written is
+    /// added to the end of the file's existing contents; if the path does not
+    /// exist, it is created.
+    ///
+    /// The returned writer implements [`tokio::io::AsyncWrite`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`tokio::fs::OpenOptions::open`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::OutputArg;
+    /// use std::env::args_os;
+    /// use tokio::io::AsyncWriteExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let logfile = args_os().nth(1)
+    ///                            .map(OutputArg::from_arg)
+    ///                            .unwrap_or_default();
+    ///     let mut f = logfile.async_append().await?;
+    ///     f.write_all(b"Another line for the log.\n").await?;
+    ///     f.flush().await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn async_append(&self) -> Result<AsyncOutputArgWriter, Error> {
+        OutputArg::options().append(true).async_open(self).await
+    }
+
+    /// Asynchronously append a slice to the end of the contents of the output
+    /// arg.
+    ///
+    /// If the output arg is the `Stdout` variant, the given data is written to
+    /// stdout.  Otherwise, if the output arg is a `Path` variant, the given
+    /// data is appended to the contents of the given path; if the path does
+    /// not exist, it is created first.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as
+    /// [`tokio::io::AsyncWriteExt::write_all`] and
+    /// [`tokio::fs::OpenOptions::open`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::OutputArg;
+    /// use std::env::args_os;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let logfile = args_os().nth(1)
+    ///                            .map(OutputArg::from_arg)
+    ///                            .unwrap_or_default();
+    ///     logfile.async_write_append("Another line for the log.\n").await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[allow(clippy::future_not_send)] // The Future is Send if C is Send
+    pub async fn async_write_append<C: AsRef<[u8]>>(&self, contents: C) -> Result<(), Error> {
+        let mut fp = self.async_append().await?;
+        async {
+            fp.write_all(contents.as_ref()).await?;
+            fp.flush().await
+        }
+        .await
+        .map_err(|e| Error::output(Operation::Write, self, e))
+    }
+
+    /// Asynchronously open the output arg for writing, failing if the output
+    /// path already exists.
+    ///
+    /// If the output arg is the `Stdout` variant, this returns a reference to
+    /// stdout.  Otherwise, if the output arg is a `Path` variant, the given
+    /// path is created and opened for writing; if the path already exists, an
+    /// error is returned and the existing file is left untouched.
+    ///
+    /// The returned writer implements [`tokio::io::AsyncWrite`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`tokio::fs::OpenOptions::open`] with
+    /// `create_new` set.  If the path already exists, the error will have a
+    /// kind of [`std::io::ErrorKind::AlreadyExists`], and its message will
+    /// name the offending path.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::OutputArg;
+    /// use std::env::args_os;
+    /// use tokio::io::AsyncWriteExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let outfile = args_os().nth(1)
+    ///                            .map(OutputArg::from_arg)
+    ///                            .unwrap_or_default();
+    ///     let mut f = outfile.async_create_new().await?;
+    ///     f.write_all(b"This file did not exist before.\n").await?;
+    ///     f.flush().await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn async_create_new(&self) -> Result<AsyncOutputArgWriter, Error> {
+        OutputArg::options().create_new(true).async_open(self).await
+    }
+
+    /// Asynchronously open the output arg for writing, either overwriting or
+    /// refusing to overwrite an existing file depending on the value of
+    /// `clobber`.
+    ///
+    /// If `clobber` is true, this is the same as
+    /// [`OutputArg::async_create()`]; otherwise, this is the same as
+    /// [`OutputArg::async_create_new()`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`OutputArg::async_create()`] or
+    /// [`OutputArg::async_create_new()`], as appropriate.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::OutputArg;
+    /// use std::env::args_os;
+    /// use tokio::io::AsyncWriteExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let force = args_os().any(|arg| arg == "--force");
+    ///     let outfile = OutputArg::from_arg("output.txt");
+    ///     let mut f = outfile.async_create_with_clobber(force).await?;
+    ///     f.write_all(b"Output goes here.\n").await?;
+    ///     f.flush().await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn async_create_with_clobber(
+        &self,
+        clobber: bool,
+    ) -> Result<AsyncOutputArgWriter, Error> {
+        if clobber {
+            self.async_create().await
+        } else {
+            self.async_create_new().await
+        }
+    }
+
+    /// Asynchronously open the output arg for writing, first backing up any
+    /// existing file at the output path according to `policy`.
+    ///
+    /// This is the async counterpart of [`OutputArg::create_with_backup()`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`OutputArg::async_create()`] and
+    /// [`std::fs::rename`].
+    pub async fn async_create_with_backup(
+        &self,
+        policy: BackupPolicy,
+    ) -> Result<AsyncOutputArgWriter, Error> {
+        OutputArg::options()
+            .backup(policy)
+            .async_open_with(self, Operation::Create)
+            .await
+    }
+
     /// Asynchronously write a slice as the entire contents of the output arg.
     ///
     /// If the output arg is the `Stdout` variant, the given data is written to
@@ -896,15 +1922,31 @@ impl OutputArg {
     /// }
     /// ```
     #[allow(clippy::future_not_send)] // The Future is Send if C is Send
-    pub async fn async_write<C: AsRef<[u8]>>(&self, contents: C) -> io::Result<()> {
+    pub async fn async_write<C: AsRef<[u8]>>(&self, contents: C) -> Result<(), Error> {
         match self {
             OutputArg::Stdout => {
                 let mut stdout = tokio::io::stdout();
-                stdout.write_all(contents.as_ref()).await?;
-                stdout.flush().await
+                async {
+                    stdout.write_all(contents.as_ref()).await?;
+                    stdout.flush().await
+                }
+                .await
             }
             OutputArg::Path(p) => tokio::fs::write(p, contents).await,
+            #[cfg(unix)]
+            OutputArg::Fd(fd) => match fd.try_clone_to_file() {
+                Ok(fp) => {
+                    let mut fp = tokio::fs::File::from_std(fp);
+                    async {
+                        fp.write_all(contents.as_ref()).await?;
+                        fp.flush().await
+                    }
+                    .await
+                }
+                Err(e) => Err(e),
+            },
         }
+        .map_err(|e| Error::output(Operation::Write, self, e))
     }
 }
 
@@ -925,6 +1967,8 @@ impl fmt::Display for OutputArg {
                 }
             }
             OutputArg::Path(p) => write!(f, "{}", p.display()),
+            #[cfg(unix)]
+            OutputArg::Fd(fd) => write!(f, "{fd}"),
         }
     }
 }
@@ -952,6 +1996,8 @@ impl From<OutputArg> for OsString {
         match arg {
             OutputArg::Stdout => OsString::from("-"),
             OutputArg::Path(p) => p.into(),
+            #[cfg(unix)]
+            OutputArg::Fd(fd) => fd.to_string().into(),
         }
     }
 }
@@ -962,10 +2008,17 @@ impl Serialize for OutputArg {
     /// Serializes [`OutputArg::Stdout`] as `"-"` (a string containing a single
     /// hyphen/dash).  Serializes [`OutputArg::Path`] as the inner [`PathBuf`];
     /// this will fail if the path is not valid UTF-8.
+    ///
+    /// On Unix, serializes the `Fd` variant as `"fd:N"`.  This does not
+    /// round-trip: deserializing the result yields a `Path` variant, as
+    /// deserialization cannot vouch for the validity of a descriptor (see
+    /// [`FdArg`]).
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         match self {
             OutputArg::Stdout => "-".serialize(serializer),
             OutputArg::Path(p) => p.serialize(serializer),
+            #[cfg(unix)]
+            OutputArg::Fd(fd) => serializer.collect_str(fd),
         }
     }
 }
@@ -975,6 +2028,9 @@ impl Serialize for OutputArg {
 impl<'de> Deserialize<'de> for OutputArg {
     /// Deserializes a [`PathBuf`] and converts it to an `OutputArg` with
     /// [`OutputArg::from_arg()`].
+    ///
+    /// A `Fd` variant is never produced, so an `"fd:N"` string (as produced
+    /// by serializing an `Fd` variant) is deserialized as a `Path` variant.
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
@@ -988,6 +2044,11 @@ impl<'de> Deserialize<'de> for OutputArg {
 /// This type implements [`std::io::BufRead`].
 pub type InputArgReader = Either<StdinLock<'static>, BufReader<fs::File>>;
 
+/// The type of the readers returned by [`InputArg::open_seekable()`].
+///
+/// This type implements [`std::io::BufRead`] and [`std::io::Seek`].
+pub type SeekableInputArgReader = Either<io::Cursor<Vec<u8>>, BufReader<fs::File>>;
+
 /// The type of the writers returned by [`OutputArg::create()`].
 ///
 /// This type implements [`std::io::Write`].
@@ -998,6 +2059,11 @@ pub type OutputArgWriter = Either<StdoutLock<'static>, fs::File>;
 /// This iterator yields instances of `std::io::Result<String>`.
 pub type Lines = io::Lines<InputArgReader>;
 
+/// The type of the iterators returned by [`InputArg::split()`].
+///
+/// This iterator yields instances of `std::io::Result<Vec<u8>>`.
+pub type Split = io::Split<InputArgReader>;
+
 cfg_if! {
     if #[cfg(feature = "tokio")] {
        /// The type of the asynchronous readers returned by
@@ -1019,6 +2085,12 @@ cfg_if! {
        /// This stream yields instances of `std::io::Result<String>`.
        #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
        pub type AsyncLines = LinesStream<tokio::io::BufReader<AsyncInputArgReader>>;
+
+       /// The type of the streams returned by [`InputArg::async_split()`].
+       ///
+       /// This stream yields instances of `std::io::Result<Vec<u8>>`.
+       #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+       pub type AsyncSplit = SplitStream<tokio::io::BufReader<AsyncInputArgReader>>;
     }
 }
 
@@ -1258,6 +2330,25 @@ mod tests {
                     }
                 );
             }
+
+            #[cfg(unix)]
+            #[test]
+            #[allow(unsafe_code)]
+            fn test_fd_json_does_not_round_trip() {
+                use std::os::fd::FromRawFd;
+                let val = Input {
+                    // SAFETY: The descriptor is never opened.
+                    path: InputArg::Fd(unsafe { FdArg::from_raw_fd(3) }),
+                };
+                let s = serde_json::to_string(&val).unwrap();
+                assert_eq!(s, r#"{"path":"fd:3"}"#);
+                assert_eq!(
+                    serde_json::from_str::<Input>(&s).unwrap(),
+                    Input {
+                        path: InputArg::Path(PathBuf::from("fd:3"))
+                    }
+                );
+            }
         }
     }
 
@@ -1452,6 +2543,15 @@ mod tests {
             require_send(fut);
         }
 
+        #[cfg(feature = "tokio")]
+        #[test]
+        fn test_async_write_append_is_send_if_content_is_send() {
+            fn require_send<T: Send>(_t: T) {}
+            let p = OutputArg::default();
+            let fut = p.async_write_append(b"This arg is Send.");
+            require_send(fut);
+        }
+
         #[cfg(feature = "serde")]
         mod serding {
             use super::*;
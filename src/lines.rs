@@ -0,0 +1,317 @@
+use crate::{Error, InputArg, InputArgReader, Operation};
+use std::fmt;
+use std::io::{self, BufRead};
+
+#[cfg(feature = "tokio")]
+use crate::AsyncInputArgReader;
+#[cfg(feature = "tokio")]
+use std::pin::Pin;
+#[cfg(feature = "tokio")]
+use std::task::{Context, Poll, ready};
+#[cfg(feature = "tokio")]
+use tokio::io::AsyncBufRead;
+#[cfg(feature = "tokio")]
+use tokio_stream::Stream;
+
+/// An iterator over the lines of an input arg as byte vectors, returned by
+/// [`InputArg::byte_lines()`][crate::InputArg::byte_lines].
+///
+/// This iterator yields instances of `std::io::Result<Vec<u8>>`.  As with
+/// [`std::io::Lines`], each line has its terminating newline (`\n`) or CR LF
+/// (`\r\n`) removed, but unlike `std::io::Lines`, lines are not required to
+/// be valid UTF-8.  A `\r` that is not followed by a `\n` is kept as part of
+/// the line.
+#[derive(Debug)]
+pub struct ByteLines(InputArgReader);
+
+impl ByteLines {
+    pub(crate) fn new(inner: InputArgReader) -> ByteLines {
+        ByteLines(inner)
+    }
+}
+
+impl Iterator for ByteLines {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<io::Result<Vec<u8>>> {
+        let mut buf = Vec::new();
+        match self.0.read_until(b'\n', &mut buf) {
+            Ok(0) => None,
+            Ok(_) => {
+                strip_newline(&mut buf);
+                Some(Ok(buf))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// An asynchronous stream over the lines of an input arg as byte vectors,
+/// returned by
+/// [`InputArg::async_byte_lines()`][crate::InputArg::async_byte_lines].
+///
+/// This stream yields instances of `std::io::Result<Vec<u8>>`, with each
+/// line's terminating newline (`\n`) or CR LF (`\r\n`) removed.  A `\r`
+/// that is not followed by a `\n` is kept as part of the line.
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+#[derive(Debug)]
+pub struct AsyncByteLines {
+    reader: tokio::io::BufReader<AsyncInputArgReader>,
+    buf: Vec<u8>,
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncByteLines {
+    pub(crate) fn new(reader: tokio::io::BufReader<AsyncInputArgReader>) -> Self {
+        AsyncByteLines {
+            reader,
+            buf: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Stream for AsyncByteLines {
+    type Item = io::Result<Vec<u8>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            let available = match ready!(Pin::new(&mut this.reader).poll_fill_buf(cx)) {
+                Ok(available) => available,
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            };
+            if available.is_empty() {
+                // EOF: yield any final unterminated line as-is
+                if this.buf.is_empty() {
+                    return Poll::Ready(None);
+                }
+                return Poll::Ready(Some(Ok(std::mem::take(&mut this.buf))));
+            }
+            let (used, done) = match available.iter().position(|&b| b == b'\n') {
+                Some(i) => (i + 1, true),
+                None => (available.len(), false),
+            };
+            this.buf.extend_from_slice(&available[..used]);
+            Pin::new(&mut this.reader).consume(used);
+            if done {
+                let mut line = std::mem::take(&mut this.buf);
+                strip_newline(&mut line);
+                return Poll::Ready(Some(Ok(line)));
+            }
+        }
+    }
+}
+
+/// An iterator over the lines of an input arg together with their locations,
+/// returned by [`InputArg::numbered_lines()`].
+///
+/// This iterator yields instances of `Result<(usize, u64, String),
+/// patharg::Error>`.  Each successful item consists of the 1-based line
+/// number, the byte offset of the start of the line within the input, and the
+/// line itself with its terminating newline (`\n`) or CR LF (`\r\n`)
+/// removed.  Errors record the input arg and, via
+/// [`Error::line_number()`], the number of the line that could not be read.
+///
+/// If a line is not valid UTF-8, an error with kind
+/// [`std::io::ErrorKind::InvalidData`] is yielded for it, and iteration can
+/// continue with the next line.
+#[derive(Debug)]
+pub struct NumberedLines {
+    arg: InputArg,
+    reader: InputArgReader,
+    line_number: usize,
+    offset: u64,
+}
+
+impl NumberedLines {
+    pub(crate) fn new(arg: InputArg, reader: InputArgReader) -> NumberedLines {
+        NumberedLines {
+            arg,
+            reader,
+            line_number: 0,
+            offset: 0,
+        }
+    }
+
+    /// Returns the input arg that the lines are read from
+    pub fn input_arg(&self) -> &InputArg {
+        &self.arg
+    }
+
+    /// Returns the 1-based line number of the most recently read line, or 0
+    /// if no line has been read yet
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+}
+
+impl Iterator for NumberedLines {
+    type Item = Result<(usize, u64, String), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut buf = Vec::new();
+        let n = match self.reader.read_until(b'\n', &mut buf) {
+            Ok(0) => return None,
+            Ok(n) => n,
+            Err(e) => {
+                return Some(Err(
+                    Error::input(Operation::Read, &self.arg, e).at_line(self.line_number + 1)
+                ));
+            }
+        };
+        self.line_number += 1;
+        let offset = self.offset;
+        self.offset += n as u64;
+        strip_newline(&mut buf);
+        Some(match String::from_utf8(buf) {
+            Ok(line) => Ok((self.line_number, offset, line)),
+            Err(e) => Err(Error::input(
+                Operation::Read,
+                &self.arg,
+                io::Error::new(io::ErrorKind::InvalidData, e.utf8_error()),
+            )
+            .at_line(self.line_number)),
+        })
+    }
+}
+
+/// An iterator over the lines of an input arg that keeps each line's
+/// terminator, returned by
+/// [`InputArg::lines_with_terminators()`][crate::InputArg::lines_with_terminators].
+///
+/// This iterator yields instances of `std::io::Result<Line>`, where each
+/// individual item has the same error conditions as
+/// [`std::io::BufRead::read_line()`].
+#[derive(Debug)]
+pub struct LinesWithTerminators(InputArgReader);
+
+impl LinesWithTerminators {
+    pub(crate) fn new(inner: InputArgReader) -> LinesWithTerminators {
+        LinesWithTerminators(inner)
+    }
+}
+
+impl Iterator for LinesWithTerminators {
+    type Item = io::Result<Line>;
+
+    fn next(&mut self) -> Option<io::Result<Line>> {
+        let mut buf = String::new();
+        match self.0.read_line(&mut buf) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(Line::new(buf))),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// A line of text together with the terminator that ended it, as yielded by
+/// [`LinesWithTerminators`].
+///
+/// A `Line` displays as the complete line, including its terminator, so
+/// writing every `Line` of an input back out reproduces the input exactly.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Line {
+    line: String,
+    terminator: Option<LineTerminator>,
+}
+
+impl Line {
+    fn new(line: String) -> Line {
+        let terminator = if line.ends_with("\r\n") {
+            Some(LineTerminator::CrLf)
+        } else if line.ends_with('\n') {
+            Some(LineTerminator::Lf)
+        } else {
+            None
+        };
+        Line { line, terminator }
+    }
+
+    /// Returns the text of the line without its terminator
+    pub fn content(&self) -> &str {
+        let len = self.terminator.map_or(0, |t| t.as_str().len());
+        &self.line[..(self.line.len() - len)]
+    }
+
+    /// Returns the line's terminator, or `None` if the line is the last line
+    /// of the input and does not end with a newline
+    pub fn terminator(&self) -> Option<LineTerminator> {
+        self.terminator
+    }
+
+    /// Returns true if the line ends with CR LF (`\r\n`)
+    pub fn is_crlf(&self) -> bool {
+        self.terminator == Some(LineTerminator::CrLf)
+    }
+
+    /// Returns the complete line, including its terminator
+    pub fn as_str(&self) -> &str {
+        &self.line
+    }
+
+    /// Consumes the `Line` and returns the complete line, including its
+    /// terminator
+    pub fn into_string(self) -> String {
+        self.line
+    }
+
+    /// Consumes the `Line` and returns the text of the line without its
+    /// terminator
+    pub fn into_content(mut self) -> String {
+        let len = self.content().len();
+        self.line.truncate(len);
+        self.line
+    }
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.line)
+    }
+}
+
+impl AsRef<str> for Line {
+    fn as_ref(&self) -> &str {
+        &self.line
+    }
+}
+
+/// The sequence of characters that ends a [`Line`]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum LineTerminator {
+    /// A line feed (`\n`), as used on Unix
+    Lf,
+
+    /// A carriage return followed by a line feed (`\r\n`), as used on
+    /// Windows
+    CrLf,
+}
+
+impl LineTerminator {
+    /// Returns the terminator as a string
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineTerminator::Lf => "\n",
+            LineTerminator::CrLf => "\r\n",
+        }
+    }
+}
+
+impl fmt::Display for LineTerminator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Remove a trailing `\n` from `line`, along with a `\r` immediately before
+/// it.  A `\r` that is not followed by a `\n` is left in place.
+fn strip_newline(line: &mut Vec<u8>) {
+    if line.last() == Some(&b'\n') {
+        line.pop();
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+    }
+}
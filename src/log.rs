@@ -0,0 +1,480 @@
+#[cfg(unix)]
+use crate::FdArg;
+use crate::{Error, Operation, OutputArg, OutputArgOptions};
+use either::Either;
+use std::ffi::OsString;
+use std::fmt;
+use std::fs;
+use std::io::{self, StderrLock, StdoutLock, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "tokio")]
+use tokio::io::AsyncWriteExt;
+#[cfg(feature = "tokio")]
+use tokio_util::either::Either as AsyncEither;
+
+/// The argument string that [`LogArg::from_arg()`] maps to
+/// [`LogArg::Stderr`]
+pub const STDERR_ARG: &str = "/dev/stderr";
+
+/// An output path that can refer to standard output, standard error, or a file
+/// system path.
+///
+/// This is a companion to [`OutputArg`] for options like `--log-file` where
+/// diagnostics may be sent to either standard stream or to a file.  As with
+/// `OutputArg`, an argument of `-` (a single hyphen/dash) refers to standard
+/// output; in addition, an argument of `/dev/stderr` (or a custom token given
+/// to [`LogArg::from_arg_with_stderr_token()`]) refers to standard error.
+///
+/// # Example
+///
+/// ```no_run
+/// use patharg::LogArg;
+/// use std::env::args_os;
+/// use std::io::{self, Write};
+///
+/// fn main() -> io::Result<()> {
+///     let logfile = args_os().nth(1)
+///                            .map(LogArg::from_arg)
+///                            .unwrap_or_default();
+///     let mut log = logfile.append()?;
+///     writeln!(&mut log, "Starting up")?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum LogArg {
+    /// Refers to standard output
+    Stdout,
+
+    /// Refers to standard error.
+    ///
+    /// This is the variant returned by `LogArg::default()`, as diagnostics
+    /// conventionally go to standard error.
+    #[default]
+    Stderr,
+
+    /// Refers to a file system path (stored in `.0`)
+    Path(PathBuf),
+
+    /// Refers to an already-open file descriptor (stored in `.0`), as
+    /// produced by an [`ArgParser`][crate::ArgParser] with
+    /// [`fd_args()`][crate::ArgParser::fd_args] enabled
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    Fd(FdArg),
+}
+
+impl LogArg {
+    /// Construct a `LogArg` from a string, usually one taken from command-line
+    /// arguments.  If the string equals `"-"`, [`LogArg::Stdout`] is
+    /// returned; if it equals [`STDERR_ARG`] (`"/dev/stderr"`),
+    /// [`LogArg::Stderr`] is returned; otherwise, a [`LogArg::Path`] is
+    /// returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use patharg::LogArg;
+    /// use std::path::PathBuf;
+    ///
+    /// assert_eq!(LogArg::from_arg("-"), LogArg::Stdout);
+    /// assert_eq!(LogArg::from_arg("/dev/stderr"), LogArg::Stderr);
+    /// assert_eq!(
+    ///     LogArg::from_arg("log.txt"),
+    ///     LogArg::Path(PathBuf::from("log.txt"))
+    /// );
+    /// ```
+    pub fn from_arg<S: Into<PathBuf>>(arg: S) -> LogArg {
+        LogArg::from_arg_with_stderr_token(arg, STDERR_ARG)
+    }
+
+    /// Construct a `LogArg` from a string like [`LogArg::from_arg()`], but
+    /// with `stderr_token` as the string that refers to standard error instead
+    /// of `"/dev/stderr"`.
+    ///
+    /// Note that the `Display` implementation always writes
+    /// [`LogArg::Stderr`] as `/dev/stderr`, so when using a custom token,
+    /// displayed values may not round-trip through this constructor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use patharg::LogArg;
+    /// use std::path::PathBuf;
+    ///
+    /// assert_eq!(LogArg::from_arg_with_stderr_token("@", "@"), LogArg::Stderr);
+    /// assert_eq!(
+    ///     LogArg::from_arg_with_stderr_token("/dev/stderr", "@"),
+    ///     LogArg::Path(PathBuf::from("/dev/stderr"))
+    /// );
+    /// ```
+    pub fn from_arg_with_stderr_token<S, T>(arg: S, stderr_token: T) -> LogArg
+    where
+        S: Into<PathBuf>,
+        T: AsRef<Path>,
+    {
+        let arg = arg.into();
+        if arg == Path::new("-") {
+            LogArg::Stdout
+        } else if arg == stderr_token.as_ref() {
+            LogArg::Stderr
+        } else {
+            LogArg::Path(arg)
+        }
+    }
+
+    /// Returns true if the log arg is the `Stdout` variant of `LogArg`.
+    pub fn is_stdout(&self) -> bool {
+        self == &LogArg::Stdout
+    }
+
+    /// Returns true if the log arg is the `Stderr` variant of `LogArg`.
+    pub fn is_stderr(&self) -> bool {
+        self == &LogArg::Stderr
+    }
+
+    /// Returns true if the log arg is the `Path` variant of `LogArg`.
+    pub fn is_path(&self) -> bool {
+        matches!(self, LogArg::Path(_))
+    }
+
+    /// Retrieve a reference to the inner [`PathBuf`].  If the log arg is the
+    /// `Stdout`, `Stderr`, or `Fd` variant, this returns `None`.
+    pub fn path_ref(&self) -> Option<&PathBuf> {
+        match self {
+            LogArg::Stdout | LogArg::Stderr => None,
+            #[cfg(unix)]
+            LogArg::Fd(_) => None,
+            LogArg::Path(p) => Some(p),
+        }
+    }
+
+    /// Retrieve a mutable reference to the inner [`PathBuf`].  If the log arg
+    /// is the `Stdout`, `Stderr`, or `Fd` variant, this returns `None`.
+    pub fn path_mut(&mut self) -> Option<&mut PathBuf> {
+        match self {
+            LogArg::Stdout | LogArg::Stderr => None,
+            #[cfg(unix)]
+            LogArg::Fd(_) => None,
+            LogArg::Path(p) => Some(p),
+        }
+    }
+
+    /// Consume the log arg and return the inner [`PathBuf`].  If the log arg
+    /// is the `Stdout`, `Stderr`, or `Fd` variant, this returns `None`.
+    pub fn into_path(self) -> Option<PathBuf> {
+        match self {
+            LogArg::Stdout | LogArg::Stderr => None,
+            #[cfg(unix)]
+            LogArg::Fd(_) => None,
+            LogArg::Path(p) => Some(p),
+        }
+    }
+
+    /// Open the log arg for writing.
+    ///
+    /// If the log arg is the `Stdout` or `Stderr` variant, this returns a
+    /// locked reference to the corresponding stream.  Otherwise, if the log
+    /// arg is a `Path` variant, the given path is opened for writing; if the
+    /// path does not exist, it is created, and if it does exist, it is
+    /// truncated.
+    ///
+    /// The returned writer implements [`std::io::Write`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`std::fs::File::create`].
+    pub fn create(&self) -> Result<LogArgWriter, Error> {
+        self.open_with(&OutputArg::options(), Operation::Create)
+    }
+
+    /// Open the log arg for appending.
+    ///
+    /// This is the same as [`LogArg::create()`], except that an existing file
+    /// at the given path is appended to rather than truncated.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`std::fs::OpenOptions::open`].
+    pub fn append(&self) -> Result<LogArgWriter, Error> {
+        self.open_with(OutputArg::options().append(true), Operation::Open)
+    }
+
+    fn open_with(&self, opts: &OutputArgOptions, op: Operation) -> Result<LogArgWriter, Error> {
+        Ok(match self {
+            LogArg::Stdout => Either::Left(Either::Left(io::stdout().lock())),
+            LogArg::Stderr => Either::Left(Either::Right(io::stderr().lock())),
+            LogArg::Path(p) => Either::Right(
+                opts.std_options()
+                    .open(p)
+                    .map_err(|e| Error::log(op, self, e))?,
+            ),
+            #[cfg(unix)]
+            LogArg::Fd(fd) => Either::Right(
+                fd.try_clone_to_file()
+                    .map_err(|e| Error::log(op, self, e))?,
+            ),
+        })
+    }
+
+    /// Write a slice as the entire contents of the log arg.
+    ///
+    /// If the log arg is the `Stdout` or `Stderr` variant, the given data is
+    /// written to the corresponding stream.  Otherwise, if the log arg is a
+    /// `Path` variant, the contents of the given path are replaced with the
+    /// given data; if the path does not exist, it is created first.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`std::io::Write::write_all`] and
+    /// [`std::fs::write`].
+    pub fn write<C: AsRef<[u8]>>(&self, contents: C) -> Result<(), Error> {
+        match self {
+            LogArg::Stdout => io::stdout().lock().write_all(contents.as_ref()),
+            LogArg::Stderr => io::stderr().lock().write_all(contents.as_ref()),
+            LogArg::Path(p) => fs::write(p, contents),
+            #[cfg(unix)]
+            LogArg::Fd(fd) => fd
+                .try_clone_to_file()
+                .and_then(|mut fp| fp.write_all(contents.as_ref())),
+        }
+        .map_err(|e| Error::log(Operation::Write, self, e))
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+impl LogArg {
+    /// Asynchronously open the log arg for writing.
+    ///
+    /// If the log arg is the `Stdout` or `Stderr` variant, this returns a
+    /// reference to the corresponding stream.  Otherwise, if the log arg is a
+    /// `Path` variant, the given path is opened for writing; if the path does
+    /// not exist, it is created, and if it does exist, it is truncated.
+    ///
+    /// The returned writer implements [`tokio::io::AsyncWrite`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`tokio::fs::File::create`].
+    pub async fn async_create(&self) -> Result<AsyncLogArgWriter, Error> {
+        self.async_open_with(&OutputArg::options(), Operation::Create)
+            .await
+    }
+
+    /// Asynchronously open the log arg for appending.
+    ///
+    /// This is the same as [`LogArg::async_create()`], except that an
+    /// existing file at the given path is appended to rather than truncated.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`tokio::fs::OpenOptions::open`].
+    pub async fn async_append(&self) -> Result<AsyncLogArgWriter, Error> {
+        self.async_open_with(OutputArg::options().append(true), Operation::Open)
+            .await
+    }
+
+    async fn async_open_with(
+        &self,
+        opts: &OutputArgOptions,
+        op: Operation,
+    ) -> Result<AsyncLogArgWriter, Error> {
+        Ok(match self {
+            LogArg::Stdout => AsyncEither::Left(AsyncEither::Left(tokio::io::stdout())),
+            LogArg::Stderr => AsyncEither::Left(AsyncEither::Right(tokio::io::stderr())),
+            LogArg::Path(p) => AsyncEither::Right(
+                tokio::fs::OpenOptions::from(opts.std_options())
+                    .open(p)
+                    .await
+                    .map_err(|e| Error::log(op, self, e))?,
+            ),
+            #[cfg(unix)]
+            LogArg::Fd(fd) => AsyncEither::Right(tokio::fs::File::from_std(
+                fd.try_clone_to_file()
+                    .map_err(|e| Error::log(op, self, e))?,
+            )),
+        })
+    }
+
+    /// Asynchronously write a slice as the entire contents of the log arg.
+    ///
+    /// If the log arg is the `Stdout` or `Stderr` variant, the given data is
+    /// written to the corresponding stream.  Otherwise, if the log arg is a
+    /// `Path` variant, the contents of the given path are replaced with the
+    /// given data; if the path does not exist, it is created first.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as
+    /// [`tokio::io::AsyncWriteExt::write_all`] and [`tokio::fs::write`].
+    #[allow(clippy::future_not_send)] // The Future is Send if C is Send
+    pub async fn async_write<C: AsRef<[u8]>>(&self, contents: C) -> Result<(), Error> {
+        match self {
+            LogArg::Stdout => {
+                let mut stdout = tokio::io::stdout();
+                async {
+                    stdout.write_all(contents.as_ref()).await?;
+                    stdout.flush().await
+                }
+                .await
+            }
+            LogArg::Stderr => {
+                let mut stderr = tokio::io::stderr();
+                async {
+                    stderr.write_all(contents.as_ref()).await?;
+                    stderr.flush().await
+                }
+                .await
+            }
+            LogArg::Path(p) => tokio::fs::write(p, contents).await,
+            #[cfg(unix)]
+            LogArg::Fd(fd) => match fd.try_clone_to_file() {
+                Ok(fp) => {
+                    let mut fp = tokio::fs::File::from_std(fp);
+                    async {
+                        fp.write_all(contents.as_ref()).await?;
+                        fp.flush().await
+                    }
+                    .await
+                }
+                Err(e) => Err(e),
+            },
+        }
+        .map_err(|e| Error::log(Operation::Write, self, e))
+    }
+}
+
+impl fmt::Display for LogArg {
+    /// Displays [`LogArg::Stdout`] as `-` (a single hyphen/dash) or as
+    /// `<stdout>` if the `{:#}` format is used.  Displays [`LogArg::Stderr`]
+    /// as `/dev/stderr` or as `<stderr>` if the `{:#}` format is used.
+    /// Always displays [`LogArg::Path`] using
+    /// [`std::path::Path::display()`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            // IMPORTANT: The default Display of the standard streams has to
+            // round-trip back to the same variants so that LogArg will work
+            // properly when used with clap's `default_value_t`.
+            LogArg::Stdout => {
+                if f.alternate() {
+                    write!(f, "<stdout>")
+                } else {
+                    write!(f, "-")
+                }
+            }
+            LogArg::Stderr => {
+                if f.alternate() {
+                    write!(f, "<stderr>")
+                } else {
+                    write!(f, "{STDERR_ARG}")
+                }
+            }
+            LogArg::Path(p) => write!(f, "{}", p.display()),
+            #[cfg(unix)]
+            LogArg::Fd(fd) => write!(f, "{fd}"),
+        }
+    }
+}
+
+impl<S: Into<PathBuf>> From<S> for LogArg {
+    /// Convert a string to a [`LogArg`] using [`LogArg::from_arg()`].
+    fn from(s: S) -> LogArg {
+        LogArg::from_arg(s)
+    }
+}
+
+impl FromStr for LogArg {
+    type Err = std::convert::Infallible;
+
+    /// Convert a string to a [`LogArg`] using [`LogArg::from_arg()`].
+    fn from_str(s: &str) -> Result<LogArg, Self::Err> {
+        Ok(LogArg::from_arg(s))
+    }
+}
+
+impl From<LogArg> for OsString {
+    /// Convert a [`LogArg`] back to an `OsString`: `LogArg::Stdout` becomes
+    /// `"-"`, `LogArg::Stderr` becomes `"/dev/stderr"`, and `LogArg::Path(p)`
+    /// becomes `p.into()`.
+    fn from(arg: LogArg) -> OsString {
+        match arg {
+            LogArg::Stdout => OsString::from("-"),
+            LogArg::Stderr => OsString::from(STDERR_ARG),
+            LogArg::Path(p) => p.into(),
+            #[cfg(unix)]
+            LogArg::Fd(fd) => fd.to_string().into(),
+        }
+    }
+}
+
+impl From<OutputArg> for LogArg {
+    /// Convert an [`OutputArg`] to a [`LogArg`]: `OutputArg::Stdout` becomes
+    /// `LogArg::Stdout`, and `OutputArg::Path(p)` becomes `LogArg::Path(p)`.
+    fn from(arg: OutputArg) -> LogArg {
+        match arg {
+            OutputArg::Stdout => LogArg::Stdout,
+            OutputArg::Path(p) => LogArg::Path(p),
+            #[cfg(unix)]
+            OutputArg::Fd(fd) => LogArg::Fd(fd),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for LogArg {
+    /// Serializes [`LogArg::Stdout`] as `"-"` and [`LogArg::Stderr`] as
+    /// `"/dev/stderr"`.  Serializes [`LogArg::Path`] as the inner
+    /// [`PathBuf`]; this will fail if the path is not valid UTF-8.
+    ///
+    /// On Unix, serializes the `Fd` variant as `"fd:N"`.  This does not
+    /// round-trip: deserializing the result yields a `Path` variant, as
+    /// deserialization cannot vouch for the validity of a descriptor (see
+    /// [`FdArg`][crate::FdArg]).
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            LogArg::Stdout => "-".serialize(serializer),
+            LogArg::Stderr => STDERR_ARG.serialize(serializer),
+            LogArg::Path(p) => p.serialize(serializer),
+            #[cfg(unix)]
+            LogArg::Fd(fd) => serializer.collect_str(fd),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for LogArg {
+    /// Deserializes a [`PathBuf`] and converts it to a `LogArg` with
+    /// [`LogArg::from_arg()`].
+    ///
+    /// A `Fd` variant is never produced, so an `"fd:N"` string (as produced
+    /// by serializing an `Fd` variant) is deserialized as a `Path` variant.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        PathBuf::deserialize(deserializer).map(LogArg::from_arg)
+    }
+}
+
+/// The type of the writers returned by [`LogArg::create()`] and
+/// [`LogArg::append()`].
+///
+/// This type implements [`std::io::Write`].
+pub type LogArgWriter = Either<Either<StdoutLock<'static>, StderrLock<'static>>, fs::File>;
+
+/// The type of the asynchronous writers returned by
+/// [`LogArg::async_create()`] and [`LogArg::async_append()`].
+///
+/// This type implements [`tokio::io::AsyncWrite`].
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub type AsyncLogArgWriter =
+    AsyncEither<AsyncEither<tokio::io::Stdout, tokio::io::Stderr>, tokio::fs::File>;
@@ -0,0 +1,135 @@
+use crate::{Error, InputArg, Operation};
+use memmap2::Mmap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::Deref;
+
+/// The contents of an input arg as returned by [`InputArg::read_mapped()`].
+///
+/// This is either a read-only memory map of a regular file or a buffer
+/// holding the contents of standard input or a non-regular file.  Either way,
+/// it dereferences to `[u8]`.
+#[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+pub struct MappedInput(Contents);
+
+enum Contents {
+    Mapped(Mmap),
+    Buffered(Vec<u8>),
+}
+
+impl MappedInput {
+    /// Returns true if the contents are memory-mapped rather than held in a
+    /// buffer
+    pub fn is_mapped(&self) -> bool {
+        matches!(self.0, Contents::Mapped(_))
+    }
+}
+
+impl Deref for MappedInput {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match &self.0 {
+            Contents::Mapped(m) => m,
+            Contents::Buffered(v) => v,
+        }
+    }
+}
+
+impl AsRef<[u8]> for MappedInput {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl fmt::Debug for MappedInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedInput")
+            .field("mapped", &self.is_mapped())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+impl InputArg {
+    /// Read the entire contents of the input arg, memory-mapping it if
+    /// possible.
+    ///
+    /// If the input arg is a `Path` variant that refers to a regular file,
+    /// the file is memory-mapped read-only, so that its contents are paged in
+    /// on demand rather than copied into memory up front.  Otherwise — if the
+    /// input arg is the `Stdin` variant, or if the path refers to a pipe or
+    /// other special file — the entire input is read into a buffer, as with
+    /// [`InputArg::read()`].
+    ///
+    /// # Safety
+    ///
+    /// If the input arg refers to a regular file, the caller must ensure that
+    /// the file is not modified, truncated, or replaced in place — by this
+    /// process or any other — for as long as the returned [`MappedInput`] is
+    /// alive.  Changes made to a mapped file while it is mapped are visible
+    /// through the returned value, violating the immutability of `&[u8]`, and
+    /// accessing a region of the map that has since been truncated away
+    /// raises `SIGBUS` (or the platform's equivalent) and crashes the
+    /// process.
+    ///
+    /// If this cannot be guaranteed, use [`InputArg::read()`] instead.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`std::fs::File::open`],
+    /// [`memmap2::Mmap::map`], and [`std::io::Read::read_to_end`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::InputArg;
+    /// use std::env::args_os;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let infile = args_os().nth(1)
+    ///                           .map(InputArg::from_arg)
+    ///                           .unwrap_or_default();
+    ///     // SAFETY: This program assumes that its input is not modified
+    ///     // while it is running.
+    ///     let input = unsafe { infile.read_mapped()? };
+    ///     let newlines = input.iter().filter(|&&b| b == b'\n').count();
+    ///     println!("Input contains {newlines} newlines");
+    ///     Ok(())
+    /// }
+    /// ```
+    #[allow(unsafe_code)]
+    pub unsafe fn read_mapped(&self) -> Result<MappedInput, Error> {
+        let InputArg::Path(p) = self else {
+            return self.read().map(|v| MappedInput(Contents::Buffered(v)));
+        };
+        let fp = fs::File::open(p).map_err(|e| Error::input(Operation::Open, self, e))?;
+        // SAFETY: The caller upholds the requirements of `map_file()`.
+        unsafe { map_file(fp) }
+            .map(MappedInput)
+            .map_err(|e| Error::input(Operation::Read, self, e))
+    }
+}
+
+/// Memory-map `fp` if it is a regular file, or else read it into a buffer
+///
+/// # Safety
+///
+/// If `fp` is a regular file, it must not be modified or truncated while the
+/// returned map is alive.
+#[allow(unsafe_code)]
+unsafe fn map_file(mut fp: fs::File) -> io::Result<Contents> {
+    if fp.metadata()?.is_file() {
+        // SAFETY: The map is read-only, and the caller guarantees that the
+        // file is not modified while mapped.
+        let map = unsafe { Mmap::map(&fp)? };
+        Ok(Contents::Mapped(map))
+    } else {
+        let mut buf = Vec::new();
+        io::Read::read_to_end(&mut fp, &mut buf)?;
+        Ok(Contents::Buffered(buf))
+    }
+}
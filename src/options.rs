@@ -0,0 +1,320 @@
+use crate::backup::{make_backup, restore_backup};
+use crate::{BackupPolicy, Error, InputArg, Operation, OutputArg, OutputArgWriter, TeeWriter};
+use either::Either;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+#[cfg(feature = "tokio")]
+use crate::AsyncOutputArgWriter;
+#[cfg(feature = "tokio")]
+use tokio_util::either::Either as AsyncEither;
+
+/// Options for configuring how an [`OutputArg`] is opened, analogous to
+/// [`std::fs::OpenOptions`].
+///
+/// A value of this type is created with [`OutputArg::options()`] and then
+/// given an output arg to open with [`open()`][OutputArgOptions::open] (or
+/// [`async_open()`][OutputArgOptions::async_open] when the `tokio` feature is
+/// enabled).  The options only affect `Path` variants; if the output arg is
+/// the `Stdout` variant, opening it always returns a reference to stdout.
+///
+/// By default, the options are set up to behave the same as
+/// [`OutputArg::create()`]: the path is opened for writing, created if it does
+/// not exist, and truncated if it does.
+///
+/// # Example
+///
+/// ```no_run
+/// use patharg::OutputArg;
+/// use std::env::args_os;
+/// use std::io::{self, Write};
+///
+/// fn main() -> io::Result<()> {
+///     let outfile = args_os().nth(1)
+///                            .map(OutputArg::from_arg)
+///                            .unwrap_or_default();
+///     let mut f = OutputArg::options()
+///         .append(true)
+///         .open(&outfile)?;
+///     writeln!(&mut f, "Another line for the output.")?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutputArgOptions {
+    append: bool,
+    truncate: bool,
+    create: bool,
+    create_new: bool,
+    distinct_from: Vec<InputArg>,
+    backup: BackupPolicy,
+    backup_suffix: OsString,
+    #[cfg(unix)]
+    mode: Option<u32>,
+    #[cfg(unix)]
+    custom_flags: i32,
+}
+
+impl OutputArgOptions {
+    pub(crate) fn new() -> OutputArgOptions {
+        OutputArgOptions {
+            append: false,
+            truncate: true,
+            create: true,
+            create_new: false,
+            distinct_from: Vec::new(),
+            backup: BackupPolicy::None,
+            backup_suffix: OsString::from("~"),
+            #[cfg(unix)]
+            mode: None,
+            #[cfg(unix)]
+            custom_flags: 0,
+        }
+    }
+
+    /// Sets the option for append mode.  See
+    /// [`std::fs::OpenOptions::append`].
+    ///
+    /// Note that, as truncation is enabled by default, enabling append mode
+    /// also requires disabling truncation; this method does so automatically
+    /// when `append` is true.
+    pub fn append(&mut self, append: bool) -> &mut Self {
+        self.append = append;
+        if append {
+            self.truncate = false;
+        }
+        self
+    }
+
+    /// Sets the option for truncating a previous file.  See
+    /// [`std::fs::OpenOptions::truncate`].
+    pub fn truncate(&mut self, truncate: bool) -> &mut Self {
+        self.truncate = truncate;
+        self
+    }
+
+    /// Sets the option to create a new file if it does not already exist.
+    /// See [`std::fs::OpenOptions::create`].
+    pub fn create(&mut self, create: bool) -> &mut Self {
+        self.create = create;
+        self
+    }
+
+    /// Sets the option to create a new file, failing if it already exists.
+    /// See [`std::fs::OpenOptions::create_new`].
+    pub fn create_new(&mut self, create_new: bool) -> &mut Self {
+        self.create_new = create_new;
+        self
+    }
+
+    /// Refuse to open the output arg if it refers to the same file as the
+    /// given input arg, as determined by [`OutputArg::conflicts_with()`].
+    ///
+    /// This guards against commands like `tool foo.txt -o foo.txt`
+    /// truncating their input before it is read.  This method can be called
+    /// multiple times to check against multiple input args.
+    pub fn distinct_from(&mut self, input: &InputArg) -> &mut Self {
+        self.distinct_from.push(input.clone());
+        self
+    }
+
+    /// Sets the policy for backing up an existing file at the output path
+    /// before opening it.  See [`BackupPolicy`].
+    ///
+    /// Backups are only made when append mode and
+    /// [`create_new`][OutputArgOptions::create_new] are both disabled; with
+    /// `create_new`, an existing file is left in place, and opening it fails
+    /// with [`std::io::ErrorKind::AlreadyExists`].
+    ///
+    /// If a backup is made but the path then cannot be opened, the backup is
+    /// renamed back to the output path before the error is returned, so the
+    /// original file is left where it was.  (A previous simple backup that
+    /// the new backup replaced is not restored.)
+    pub fn backup(&mut self, policy: BackupPolicy) -> &mut Self {
+        self.backup = policy;
+        self
+    }
+
+    /// Sets the suffix appended to file names to form simple backup paths.
+    /// The default is `~`.
+    pub fn backup_suffix<S: Into<OsString>>(&mut self, suffix: S) -> &mut Self {
+        self.backup_suffix = suffix.into();
+        self
+    }
+
+    /// Sets the mode bits that a new file will be created with.  See
+    /// [`std::os::unix::fs::OpenOptionsExt::mode`].
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn mode(&mut self, mode: u32) -> &mut Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Pass custom flags to the `flags` argument of `open`.  See
+    /// [`std::os::unix::fs::OpenOptionsExt::custom_flags`].
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn custom_flags(&mut self, flags: i32) -> &mut Self {
+        self.custom_flags = flags;
+        self
+    }
+
+    /// Open the given output arg for writing with the options specified by
+    /// `self`.
+    ///
+    /// If the output arg is the `Stdout` variant, this returns a locked
+    /// reference to stdout.  Otherwise, if the output arg is a `Path` variant,
+    /// the given path is opened for writing using the configured options.
+    /// If the output arg is an `Fd` variant, the file descriptor is duplicated
+    /// as-is, and the options other than
+    /// [`distinct_from()`][OutputArgOptions::distinct_from] have no effect.
+    ///
+    /// The returned writer implements [`std::io::Write`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`std::fs::OpenOptions::open`].  In
+    /// addition, if the output arg refers to the same file as an input arg
+    /// passed to [`distinct_from()`][OutputArgOptions::distinct_from], an
+    /// error of kind [`std::io::ErrorKind::InvalidInput`] is returned without
+    /// opening anything.  If a backup policy is set, this also has the same
+    /// error conditions as [`std::fs::rename`].
+    pub fn open(&self, arg: &OutputArg) -> Result<OutputArgWriter, Error> {
+        self.open_with(arg, Operation::Open)
+    }
+
+    /// Open `arg`, reporting a failure to open it as an `op` error
+    pub(crate) fn open_with(
+        &self,
+        arg: &OutputArg,
+        op: Operation,
+    ) -> Result<OutputArgWriter, Error> {
+        let backup = self.prepare(arg)?;
+        Ok(match arg {
+            OutputArg::Stdout => Either::Left(io::stdout().lock()),
+            OutputArg::Path(p) => Either::Right(self.std_options().open(p).map_err(|e| {
+                restore_backup(backup, p);
+                Error::output(op, arg, e)
+            })?),
+            #[cfg(unix)]
+            OutputArg::Fd(fd) => Either::Right(
+                fd.try_clone_to_file()
+                    .map_err(|e| Error::output(op, arg, e))?,
+            ),
+        })
+    }
+
+    /// Open each of the given output args for writing with the options
+    /// specified by `self`, and return a [`TeeWriter`] that writes to all of
+    /// them.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`open()`][OutputArgOptions::open].
+    /// If any output arg cannot be opened, the error for the first such arg is
+    /// returned.
+    pub fn open_tee(&self, args: &[OutputArg]) -> Result<TeeWriter, Error> {
+        TeeWriter::open(args, self)
+    }
+
+    /// Perform the checks & backups that must happen before `arg` is opened,
+    /// returning the path of the backup made, if any
+    fn prepare(&self, arg: &OutputArg) -> Result<Option<PathBuf>, Error> {
+        self.check_distinct(arg)?;
+        if let OutputArg::Path(p) = arg
+            && !self.append
+            && !self.create_new
+        {
+            make_backup(self.backup, &self.backup_suffix, p)
+                .map_err(|e| Error::output(Operation::Backup, arg, e))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn check_distinct(&self, arg: &OutputArg) -> Result<(), Error> {
+        for input in &self.distinct_from {
+            if arg.conflicts_with(input)? {
+                return Err(Error::output(
+                    Operation::Open,
+                    arg,
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("output is the same file as input {input:#}"),
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn std_options(&self) -> fs::OpenOptions {
+        let mut opts = fs::OpenOptions::new();
+        opts.write(true)
+            .append(self.append)
+            .truncate(self.truncate)
+            .create(self.create)
+            .create_new(self.create_new);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            if let Some(mode) = self.mode {
+                opts.mode(mode);
+            }
+            opts.custom_flags(self.custom_flags);
+        }
+        opts
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+impl OutputArgOptions {
+    /// Asynchronously open the given output arg for writing with the options
+    /// specified by `self`.
+    ///
+    /// If the output arg is the `Stdout` variant, this returns a reference to
+    /// stdout.  Otherwise, if the output arg is a `Path` variant, the given
+    /// path is opened for writing using the configured options.
+    ///
+    /// The returned writer implements [`tokio::io::AsyncWrite`].
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`tokio::fs::OpenOptions::open`], plus
+    /// the additional conditions described for
+    /// [`open()`][OutputArgOptions::open].
+    pub async fn async_open(&self, arg: &OutputArg) -> Result<AsyncOutputArgWriter, Error> {
+        self.async_open_with(arg, Operation::Open).await
+    }
+
+    /// Asynchronously open `arg`, reporting a failure to open it as an `op`
+    /// error
+    pub(crate) async fn async_open_with(
+        &self,
+        arg: &OutputArg,
+        op: Operation,
+    ) -> Result<AsyncOutputArgWriter, Error> {
+        let backup = self.prepare(arg)?;
+        Ok(match arg {
+            OutputArg::Stdout => AsyncEither::Left(tokio::io::stdout()),
+            OutputArg::Path(p) => AsyncEither::Right(
+                tokio::fs::OpenOptions::from(self.std_options())
+                    .open(p)
+                    .await
+                    .map_err(|e| {
+                        restore_backup(backup, p);
+                        Error::output(op, arg, e)
+                    })?,
+            ),
+            #[cfg(unix)]
+            OutputArg::Fd(fd) => AsyncEither::Right(tokio::fs::File::from_std(
+                fd.try_clone_to_file()
+                    .map_err(|e| Error::output(op, arg, e))?,
+            )),
+        })
+    }
+}
@@ -0,0 +1,228 @@
+#[cfg(unix)]
+use crate::FdArg;
+use crate::{InPlaceArg, InputArg, LogArg, OutputArg, STDERR_ARG};
+use std::ffi::OsString;
+#[cfg(unix)]
+use std::os::fd::{AsRawFd, FromRawFd};
+use std::path::{Path, PathBuf};
+
+/// A configurable parser for converting command-line arguments into
+/// [`InputArg`], [`OutputArg`], [`InPlaceArg`], and [`LogArg`] values.
+///
+/// The `from_arg()` constructors of those types always treat `-` (a single
+/// hyphen/dash) as referring to the appropriate standard stream.  An
+/// `ArgParser` allows changing or disabling this sentinel and, optionally,
+/// recognizing the device paths `/dev/stdin`, `/dev/stdout`, `/dev/stderr`, and
+/// `/dev/fd/N` (for `N` of 0, 1, or 2) as the corresponding standard streams
+/// rather than as file paths.
+///
+/// On Unix, an `ArgParser` can also be configured to accept arguments of the
+/// form `fd:N` or `/dev/fd/N` referring to inherited file descriptors; see
+/// [`ArgParser::fd_args()`].
+///
+/// By default, an `ArgParser` behaves the same as the `from_arg()`
+/// constructors.
+///
+/// # Example
+///
+/// ```
+/// use patharg::{ArgParser, InputArg, OutputArg};
+/// use std::path::PathBuf;
+///
+/// let mut parser = ArgParser::new();
+/// parser.sentinel("@").dev_paths(true);
+///
+/// assert_eq!(parser.parse_input("@"), InputArg::Stdin);
+/// assert_eq!(parser.parse_input("/dev/stdin"), InputArg::Stdin);
+/// assert_eq!(
+///     parser.parse_input("-"),
+///     InputArg::Path(PathBuf::from("-"))
+/// );
+/// assert_eq!(parser.parse_output("/dev/fd/1"), OutputArg::Stdout);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArgParser {
+    sentinel: Option<OsString>,
+    dev_paths: bool,
+    #[cfg(unix)]
+    fd_args: bool,
+}
+
+impl ArgParser {
+    /// Create a new `ArgParser` that treats `-` as the standard-stream
+    /// sentinel and does not recognize device paths
+    pub fn new() -> ArgParser {
+        ArgParser {
+            sentinel: Some(OsString::from("-")),
+            dev_paths: false,
+            #[cfg(unix)]
+            fd_args: false,
+        }
+    }
+
+    /// Set the argument string that refers to the standard streams.  The
+    /// default is `-`.
+    pub fn sentinel<S: Into<OsString>>(&mut self, sentinel: S) -> &mut Self {
+        self.sentinel = Some(sentinel.into());
+        self
+    }
+
+    /// Disable the standard-stream sentinel, so that no argument string
+    /// (other than device paths, if enabled) refers to the standard streams
+    pub fn no_sentinel(&mut self) -> &mut Self {
+        self.sentinel = None;
+        self
+    }
+
+    /// Set whether to recognize `/dev/stdin`, `/dev/stdout`, `/dev/stderr`,
+    /// `/dev/fd/0`, `/dev/fd/1`, and `/dev/fd/2` as referring to the
+    /// corresponding standard streams.  The default is `false`.
+    ///
+    /// Note that [`LogArg::from_arg()`] always recognizes `/dev/stderr`
+    /// regardless of this setting, and so does [`ArgParser::parse_log()`].
+    pub fn dev_paths(&mut self, yes: bool) -> &mut Self {
+        self.dev_paths = yes;
+        self
+    }
+
+    /// Set whether to accept arguments of the form `fd:N` or `/dev/fd/N`
+    /// (where `N` is a nonnegative decimal integer without a sign) as
+    /// referring to the already-open file descriptor `N`, such as a pipe
+    /// passed to the process by its parent.  The default is `false`.
+    ///
+    /// Such arguments are parsed into the `Fd` variants of [`InputArg`],
+    /// [`OutputArg`], and [`LogArg`].  Opening one of these duplicates the
+    /// descriptor rather than opening any path, so reading & writing pick up
+    /// at the descriptor's current offset, honor its flags (such as
+    /// `O_APPEND`), and never truncate it; see [`FdArg`] for details.  When
+    /// device paths are also enabled with [`ArgParser::dev_paths()`], `fd:0`
+    /// & `/dev/fd/0` refer to standard input and `fd:1`, `fd:2`, `/dev/fd/1`,
+    /// & `/dev/fd/2` refer to standard output & standard error.
+    ///
+    /// # Safety
+    ///
+    /// When enabled, every `fd:N` or `/dev/fd/N` argument parsed by this
+    /// parser is turned
+    /// into an [`FdArg`] as if by [`FdArg::from_raw_fd()`], so the caller must
+    /// ensure that any such argument names a descriptor that meets the
+    /// requirements of that function: it must be open, and it must stay open
+    /// for as long as the parsed arg is in use.  Only enable this option if
+    /// the program's arguments are supplied by a trusted parent process that
+    /// passes the named descriptors to it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use patharg::{ArgParser, InputArg, OutputArg};
+    /// use std::path::PathBuf;
+    ///
+    /// let mut parser = ArgParser::new();
+    /// // SAFETY: Only descriptors 0 through 2 are named below, and these are
+    /// // always open.
+    /// unsafe { parser.fd_args(true) };
+    /// assert!(matches!(parser.parse_input("fd:0"), InputArg::Fd(_)));
+    /// assert!(matches!(parser.parse_output("fd:2"), OutputArg::Fd(_)));
+    /// assert_eq!(
+    ///     parser.parse_input("fd:+0"),
+    ///     InputArg::Path(PathBuf::from("fd:+0"))
+    /// );
+    /// ```
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    #[allow(unsafe_code)]
+    pub unsafe fn fd_args(&mut self, yes: bool) -> &mut Self {
+        self.fd_args = yes;
+        self
+    }
+
+    /// Parse an argument as an [`InputArg`]
+    pub fn parse_input<S: Into<PathBuf>>(&self, arg: S) -> InputArg {
+        let arg = arg.into();
+        #[cfg(unix)]
+        match self.fd_arg(&arg) {
+            Some(fd) if self.dev_paths && fd.as_raw_fd() == 0 => return InputArg::Stdin,
+            Some(fd) => return InputArg::Fd(fd),
+            None => (),
+        }
+        if self.is_sentinel(&arg) || self.is_dev_path(&arg, "/dev/stdin", 0) {
+            InputArg::Stdin
+        } else {
+            InputArg::Path(arg)
+        }
+    }
+
+    /// Parse an argument as an [`OutputArg`]
+    pub fn parse_output<S: Into<PathBuf>>(&self, arg: S) -> OutputArg {
+        let arg = arg.into();
+        #[cfg(unix)]
+        match self.fd_arg(&arg) {
+            Some(fd) if self.dev_paths && fd.as_raw_fd() == 1 => return OutputArg::Stdout,
+            Some(fd) => return OutputArg::Fd(fd),
+            None => (),
+        }
+        if self.is_sentinel(&arg) || self.is_dev_path(&arg, "/dev/stdout", 1) {
+            OutputArg::Stdout
+        } else {
+            OutputArg::Path(arg)
+        }
+    }
+
+    /// Parse an argument as an [`InPlaceArg`].  Device paths are never
+    /// treated specially by this method.
+    pub fn parse_in_place<S: Into<PathBuf>>(&self, arg: S) -> InPlaceArg {
+        let arg = arg.into();
+        if self.is_sentinel(&arg) {
+            InPlaceArg::Stdio
+        } else {
+            InPlaceArg::Path(arg)
+        }
+    }
+
+    /// Parse an argument as a [`LogArg`]
+    pub fn parse_log<S: Into<PathBuf>>(&self, arg: S) -> LogArg {
+        let arg = arg.into();
+        #[cfg(unix)]
+        match self.fd_arg(&arg) {
+            Some(fd) if self.dev_paths && fd.as_raw_fd() == 1 => return LogArg::Stdout,
+            Some(fd) if self.dev_paths && fd.as_raw_fd() == 2 => return LogArg::Stderr,
+            Some(fd) => return LogArg::Fd(fd),
+            None => (),
+        }
+        if self.is_sentinel(&arg) || self.is_dev_path(&arg, "/dev/stdout", 1) {
+            LogArg::Stdout
+        } else if arg == Path::new(STDERR_ARG) || self.is_dev_path(&arg, "/dev/stderr", 2) {
+            LogArg::Stderr
+        } else {
+            LogArg::Path(arg)
+        }
+    }
+
+    /// If `fd:N` arguments are enabled and `arg` is of the form `fd:N` or
+    /// `/dev/fd/N`, return the corresponding [`FdArg`]
+    #[cfg(unix)]
+    fn fd_arg(&self, arg: &Path) -> Option<FdArg> {
+        if !self.fd_args {
+            return None;
+        }
+        let fd = FdArg::parse(arg)?;
+        // SAFETY: The caller of `fd_args()` guaranteed that all `fd:N` and
+        // `/dev/fd/N` arguments name open descriptors.
+        #[allow(unsafe_code)]
+        Some(unsafe { FdArg::from_raw_fd(fd) })
+    }
+
+    fn is_sentinel(&self, arg: &Path) -> bool {
+        self.sentinel.as_ref().is_some_and(|s| arg == Path::new(s))
+    }
+
+    fn is_dev_path(&self, arg: &Path, name: &str, fd: u32) -> bool {
+        self.dev_paths && (arg == Path::new(name) || arg == Path::new(&format!("/dev/fd/{fd}")))
+    }
+}
+
+impl Default for ArgParser {
+    /// Same as [`ArgParser::new()`]
+    fn default() -> ArgParser {
+        ArgParser::new()
+    }
+}
@@ -0,0 +1,47 @@
+use crate::SeekableInputArgReader;
+use either::Either;
+use std::fs;
+use std::io::{self, BufReader, Cursor, Read, Seek, SeekFrom, Write};
+
+/// The default maximum number of bytes of a non-seekable input that
+/// [`InputArg::open_seekable()`][crate::InputArg::open_seekable] will hold in
+/// memory before spooling the input to a temporary file instead
+pub const DEFAULT_SPOOL_THRESHOLD: usize = 1 << 20;
+
+/// Read all of `reader` into a seekable buffer, keeping it in memory if it is
+/// no more than `threshold` bytes long and writing it to an anonymous
+/// temporary file otherwise
+pub(crate) fn spool<R: Read>(
+    mut reader: R,
+    threshold: usize,
+) -> io::Result<SeekableInputArgReader> {
+    let mut buf = Vec::new();
+    let limit = u64::try_from(threshold)
+        .unwrap_or(u64::MAX)
+        .saturating_add(1);
+    (&mut reader).take(limit).read_to_end(&mut buf)?;
+    if buf.len() <= threshold {
+        return Ok(Either::Left(Cursor::new(buf)));
+    }
+    let mut tmpfile = tempfile::tempfile()?;
+    tmpfile.write_all(&buf)?;
+    drop(buf);
+    io::copy(&mut reader, &mut tmpfile)?;
+    tmpfile.seek(SeekFrom::Start(0))?;
+    Ok(Either::Right(BufReader::new(tmpfile)))
+}
+
+/// Return a seekable reader for `file`, spooling its contents if it does not
+/// support seeking (e.g., because it is a pipe) or if it is not positioned at
+/// the start of the file (as can happen for an inherited descriptor), so that
+/// offset 0 of the returned reader is always where the input begins
+pub(crate) fn seekable_file(
+    mut file: fs::File,
+    threshold: usize,
+) -> io::Result<SeekableInputArgReader> {
+    if file.stream_position().is_ok_and(|pos| pos == 0) {
+        Ok(Either::Right(BufReader::new(file)))
+    } else {
+        spool(file, threshold)
+    }
+}
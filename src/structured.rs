@@ -0,0 +1,169 @@
+use crate::{Error, InputArg, Operation};
+use serde::de::DeserializeOwned;
+use std::io;
+
+#[cfg(feature = "json")]
+use crate::OutputArg;
+#[cfg(feature = "json")]
+use serde::Serialize;
+#[cfg(feature = "json")]
+use std::io::{BufWriter, Write};
+
+impl InputArg {
+    /// Read the input arg and deserialize its contents as a JSON value of
+    /// type `T`.
+    ///
+    /// The input is read from the reader returned by [`InputArg::open()`],
+    /// so stdin is supported.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::open()`].  If reading
+    /// fails, an [`Operation::Read`] error is returned.  If the input is not
+    /// valid JSON or does not match `T`, an [`Operation::Parse`] error with
+    /// kind [`std::io::ErrorKind::InvalidData`] is returned; its message
+    /// names the input arg and the location of the problem.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::InputArg;
+    /// use serde::Deserialize;
+    /// use std::env::args_os;
+    /// use std::io;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     name: String,
+    /// }
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let infile = args_os().nth(1)
+    ///                           .map(InputArg::from_arg)
+    ///                           .unwrap_or_default();
+    ///     let config = infile.read_json::<Config>()?;
+    ///     println!("Hello, {}!", config.name);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn read_json<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        serde_json::from_reader(self.open()?).map_err(|e| {
+            if e.is_io() {
+                Error::input(Operation::Read, self, e.into())
+            } else {
+                // Converting a `serde_json::Error` to an `io::Error` gives
+                // truncated input the kind `UnexpectedEof`; report every
+                // parse failure as `InvalidData` instead.
+                Error::input(Operation::Parse, self, invalid_data(e))
+            }
+        })
+    }
+
+    /// Read the input arg and deserialize its contents as a TOML document of
+    /// type `T`.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::read_to_string()`].  If
+    /// the input is not valid TOML or does not match `T`, an
+    /// [`Operation::Parse`] error with kind
+    /// [`std::io::ErrorKind::InvalidData`] is returned; its message names the
+    /// input arg and the location of the problem.
+    #[cfg(feature = "toml")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
+    pub fn read_toml<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        let s = self.read_to_string()?;
+        toml::from_str(&s).map_err(|e| Error::input(Operation::Parse, self, invalid_data(e)))
+    }
+
+    /// Read the input arg and deserialize its contents as a YAML document of
+    /// type `T`.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`InputArg::read()`], so a failure to
+    /// read is reported as an [`Operation::Read`] error.  If the input is not
+    /// valid YAML or does not match `T`, an [`Operation::Parse`] error with
+    /// kind [`std::io::ErrorKind::InvalidData`] is returned; its message names
+    /// the input arg and the location of the problem.
+    #[cfg(feature = "yaml")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "yaml")))]
+    pub fn read_yaml<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        let bytes = self.read()?;
+        serde_yaml_ng::from_slice(&bytes)
+            .map_err(|e| Error::input(Operation::Parse, self, invalid_data(e)))
+    }
+}
+
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+impl OutputArg {
+    /// Serialize `value` as compact JSON and write it, followed by a newline,
+    /// to the output arg.
+    ///
+    /// The output arg is opened as with [`OutputArg::create()`], and the
+    /// value is written through a buffer.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`OutputArg::create()`].  If
+    /// serializing or writing fails, an [`Operation::Write`] error is
+    /// returned.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use patharg::OutputArg;
+    /// use std::collections::BTreeMap;
+    /// use std::env::args_os;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let outfile = args_os().nth(1)
+    ///                            .map(OutputArg::from_arg)
+    ///                            .unwrap_or_default();
+    ///     let counts = BTreeMap::from([("apples", 3), ("oranges", 5)]);
+    ///     outfile.write_json(&counts)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn write_json<T: ?Sized + Serialize>(&self, value: &T) -> Result<(), Error> {
+        self.write_json_with(value, false)
+    }
+
+    /// Serialize `value` as pretty-printed JSON and write it, followed by a
+    /// newline, to the output arg.
+    ///
+    /// This is the same as [`OutputArg::write_json()`], except that the JSON
+    /// is indented.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`OutputArg::write_json()`].
+    pub fn write_json_pretty<T: ?Sized + Serialize>(&self, value: &T) -> Result<(), Error> {
+        self.write_json_with(value, true)
+    }
+
+    fn write_json_with<T: ?Sized + Serialize>(&self, value: &T, pretty: bool) -> Result<(), Error> {
+        let mut fp = BufWriter::new(self.create()?);
+        if pretty {
+            serde_json::to_writer_pretty(&mut fp, value)
+        } else {
+            serde_json::to_writer(&mut fp, value)
+        }
+        .map_err(io::Error::from)
+        .and_then(|()| fp.write_all(b"\n"))
+        .and_then(|()| fp.flush())
+        .map_err(|e| Error::output(Operation::Write, self, e))
+    }
+}
+
+/// Wrap a parse error from a format library in an [`io::Error`]
+pub(crate) fn invalid_data<E>(e: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
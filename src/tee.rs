@@ -0,0 +1,95 @@
+use crate::{Error, Operation, OutputArg, OutputArgOptions, OutputArgWriter};
+use std::io::{self, Write};
+
+/// A writer that writes all data to each of several output args, like the
+/// `tee` command.
+///
+/// A `TeeWriter` is created with [`TeeWriter::create()`] or
+/// [`OutputArgOptions::open_tee()`].  Each call to
+/// [`write()`][Write::write] writes the entire buffer to every output arg in
+/// order.  If writing to one of the output args fails, the data is still
+/// written to the remaining output args, and then an error for the first
+/// failure is returned; the returned [`std::io::Error`] wraps a [`patharg::Error`][Error] that records which
+/// output arg failed; it can be retrieved with
+/// [`std::io::Error::get_ref()`] and
+/// [`downcast_ref()`][std::error::Error#method.downcast_ref].
+///
+/// # Example
+///
+/// ```no_run
+/// use patharg::{OutputArg, TeeWriter};
+/// use std::io::{self, Write};
+///
+/// fn main() -> io::Result<()> {
+///     let outputs = [OutputArg::from_arg("saved.txt"), OutputArg::Stdout];
+///     let mut f = TeeWriter::create(&outputs)?;
+///     writeln!(&mut f, "This goes to both the file and stdout.")?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct TeeWriter(Vec<(OutputArg, OutputArgWriter)>);
+
+impl TeeWriter {
+    /// Open each of the given output args for writing as with
+    /// [`OutputArg::create()`] and return a writer that writes to all of them.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`OutputArg::create()`].  If any
+    /// output arg cannot be opened, the error for the first such arg is
+    /// returned.
+    pub fn create(args: &[OutputArg]) -> Result<TeeWriter, Error> {
+        args.iter()
+            .map(|arg| Ok((arg.clone(), arg.create()?)))
+            .collect::<Result<Vec<_>, Error>>()
+            .map(TeeWriter)
+    }
+
+    pub(crate) fn open(args: &[OutputArg], opts: &OutputArgOptions) -> Result<TeeWriter, Error> {
+        args.iter()
+            .map(|arg| Ok((arg.clone(), opts.open(arg)?)))
+            .collect::<Result<Vec<_>, Error>>()
+            .map(TeeWriter)
+    }
+
+    /// Returns an iterator over the output args that the writer writes to
+    pub fn output_args(&self) -> impl Iterator<Item = &OutputArg> {
+        self.0.iter().map(|(arg, _)| arg)
+    }
+
+    /// Call `f` on every writer, even after one of them fails, and return the
+    /// first error encountered
+    fn for_each_writer<F>(&mut self, mut f: F) -> io::Result<()>
+    where
+        F: FnMut(&mut OutputArgWriter) -> io::Result<()>,
+    {
+        let mut first_err = None;
+        for (arg, writer) in &mut self.0 {
+            if let Err(e) = f(writer)
+                && first_err.is_none()
+            {
+                first_err = Some(Error::output(Operation::Write, arg, e));
+            }
+        }
+        match first_err {
+            Some(e) => Err(e.into()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Write for TeeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.for_each_writer(|writer| writer.write_all(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.for_each_writer(Write::flush)
+    }
+}
@@ -0,0 +1,30 @@
+use clap::{Parser, ValueEnum};
+use patharg::{InputArg, InputArgs, StdinPolicy};
+use std::io;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Policy {
+    Allow,
+    Deny,
+    Empty,
+}
+
+#[derive(Parser)]
+struct Arguments {
+    #[arg(long, value_enum, default_value = "allow")]
+    stdin_policy: Policy,
+
+    infiles: Vec<InputArg>,
+}
+
+fn main() -> io::Result<()> {
+    let args = Arguments::parse();
+    let mut inputs = InputArgs::new(args.infiles);
+    inputs.stdin_policy(match args.stdin_policy {
+        Policy::Allow => StdinPolicy::AllowReuse,
+        Policy::Deny => StdinPolicy::DenyReuse,
+        Policy::Empty => StdinPolicy::EmptyOnReuse,
+    });
+    io::copy(&mut inputs.open(), &mut io::stdout().lock())?;
+    Ok(())
+}
@@ -14,5 +14,6 @@ fn main() -> std::io::Result<()> {
     let args = Arguments::parse();
     let mut input = args.infile.read()?;
     input.reverse();
-    args.outfile.write(input)
+    args.outfile.write(input)?;
+    Ok(())
 }
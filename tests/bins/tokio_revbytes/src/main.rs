@@ -15,5 +15,6 @@ async fn main() -> std::io::Result<()> {
     let args = Arguments::parse();
     let mut input = args.infile.async_read().await?;
     input.reverse();
-    args.outfile.async_write(input).await
+    args.outfile.async_write(input).await?;
+    Ok(())
 }
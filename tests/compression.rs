@@ -0,0 +1,153 @@
+#![allow(missing_docs)]
+#![cfg(feature = "compression")]
+use assert_fs::TempDir;
+use assert_fs::prelude::*;
+use patharg::{Compression, InputArg, OutputArg};
+use rstest::rstest;
+use std::io::Read;
+
+static TEXT: &str = "Hello, world!\nSecond line\n";
+
+static GZIP: &[u8] = b"\x1F\x8B\x08\x00\x00\x00\x00\x00\x02\x03\xF3\x48\xCD\xC9\xC9\xD7\x51\x28\xCF\x2F\xCA\x49\x51\xE4\x0A\x4E\x4D\xCE\xCF\x4B\x51\xC8\xC9\xCC\x4B\xE5\x02\x00\x78\xCC\xEE\x4A\x1A\x00\x00\x00";
+
+static BZIP2: &[u8] = b"\x42\x5A\x68\x39\x31\x41\x59\x26\x53\x59\x4D\xE3\xFC\xFE\x00\x00\x03\x57\x80\x00\x10\x60\x04\x00\x40\x08\x00\x0E\x25\x90\x80\x20\x00\x22\x9A\x69\xE9\x3D\x4C\xD2\x0A\x60\x00\x0B\x90\xC8\x60\x24\xD3\x2F\x75\x0E\x6F\x57\xED\xC7\xC5\xDC\x91\x4E\x14\x24\x13\x78\xFF\x3F\x80";
+
+static XZ: &[u8] = b"\xFD\x37\x7A\x58\x5A\x00\x00\x04\xE6\xD6\xB4\x46\x02\x00\x21\x01\x16\x00\x00\x00\x74\x2F\xE5\xA3\x01\x00\x19\x48\x65\x6C\x6C\x6F\x2C\x20\x77\x6F\x72\x6C\x64\x21\x0A\x53\x65\x63\x6F\x6E\x64\x20\x6C\x69\x6E\x65\x0A\x00\x00\x00\xD0\xBB\x21\x27\x5F\x33\x82\xA9\x00\x01\x32\x1A\x20\x18\x94\x30\x1F\xB6\xF3\x7D\x01\x00\x00\x00\x00\x04\x59\x5A";
+
+static ZSTD: &[u8] = b"\x28\xB5\x2F\xFD\x04\x58\xD1\x00\x00\x48\x65\x6C\x6C\x6F\x2C\x20\x77\x6F\x72\x6C\x64\x21\x0A\x53\x65\x63\x6F\x6E\x64\x20\x6C\x69\x6E\x65\x0A\xD8\x04\x47\x95";
+
+#[rstest]
+#[case("data.txt.gz", GZIP, Some(Compression::Gzip))]
+#[case("data.txt.bz2", BZIP2, Some(Compression::Bzip2))]
+#[case("data.txt.xz", XZ, Some(Compression::Xz))]
+#[case("data.txt.zst", ZSTD, Some(Compression::Zstd))]
+#[case("data-gz", GZIP, Some(Compression::Gzip))]
+#[case("data-bz2", BZIP2, Some(Compression::Bzip2))]
+#[case("data-xz", XZ, Some(Compression::Xz))]
+#[case("data-zst", ZSTD, Some(Compression::Zstd))]
+#[case("data.txt", TEXT.as_bytes(), None)]
+fn test_open_decompressed(
+    #[case] name: &str,
+    #[case] data: &[u8],
+    #[case] compression: Option<Compression>,
+) {
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.child(name);
+    path.write_binary(data).unwrap();
+    let infile = InputArg::from_arg(path.path());
+    let mut reader = infile.open_decompressed().unwrap();
+    assert_eq!(reader.compression(), compression);
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, TEXT);
+}
+
+#[cfg(unix)]
+#[test]
+#[allow(unsafe_code)]
+fn test_open_decompressed_short_first_read() {
+    use patharg::FdArg;
+    use std::io::Write;
+    use std::os::fd::{AsRawFd, FromRawFd};
+    use std::thread;
+    use std::time::Duration;
+    let (reader, mut writer) = std::io::pipe().unwrap();
+    // Only part of the xz magic number is available when the input is opened.
+    writer.write_all(&XZ[..3]).unwrap();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        writer.write_all(&XZ[3..]).unwrap();
+    });
+    // SAFETY: `reader` stays open for the rest of the test.
+    let infile = InputArg::Fd(unsafe { FdArg::from_raw_fd(reader.as_raw_fd()) });
+    let mut decoded = infile.open_decompressed().unwrap();
+    assert_eq!(decoded.compression(), Some(Compression::Xz));
+    let mut s = String::new();
+    decoded.read_to_string(&mut s).unwrap();
+    handle.join().unwrap();
+    assert_eq!(s, TEXT);
+}
+
+#[test]
+fn test_lines_decompressed() {
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.child("data.txt.gz");
+    path.write_binary(GZIP).unwrap();
+    let infile = InputArg::from_arg(path.path());
+    let lines = infile
+        .lines_decompressed()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(lines, ["Hello, world!", "Second line"]);
+}
+
+#[test]
+fn test_read_decompressed_empty() {
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.child("empty.txt");
+    path.touch().unwrap();
+    let infile = InputArg::from_arg(path.path());
+    assert_eq!(infile.read_decompressed().unwrap(), b"");
+}
+
+#[rstest]
+#[case("out.txt.gz", Some(Compression::Gzip))]
+#[case("out.txt.bz2", Some(Compression::Bzip2))]
+#[case("out.txt.xz", Some(Compression::Xz))]
+#[case("out.txt.zst", Some(Compression::Zstd))]
+#[case("out.txt", None)]
+fn test_create_compressed(#[case] name: &str, #[case] compression: Option<Compression>) {
+    use std::io::Write;
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.child(name);
+    let outfile = OutputArg::from_arg(path.path());
+    let mut writer = outfile.create_compressed().unwrap();
+    assert_eq!(writer.compression(), compression);
+    writer.write_all(TEXT.as_bytes()).unwrap();
+    writer.finish().unwrap();
+    let infile = InputArg::from_arg(path.path());
+    let mut reader = infile.open_decompressed().unwrap();
+    assert_eq!(reader.compression(), compression);
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, TEXT);
+}
+
+#[test]
+fn test_create_with_compression_overrides_extension() {
+    use std::io::Write;
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.child("out.txt");
+    let outfile = OutputArg::from_arg(path.path());
+    let mut writer = outfile
+        .create_with_compression(Some(Compression::Zstd))
+        .unwrap();
+    writer.write_all(TEXT.as_bytes()).unwrap();
+    drop(writer);
+    let infile = InputArg::from_arg(path.path());
+    let mut reader = infile.open_decompressed().unwrap();
+    assert_eq!(reader.compression(), Some(Compression::Zstd));
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, TEXT);
+}
+
+#[cfg(feature = "tokio")]
+#[rstest]
+#[case("out.txt.gz")]
+#[case("out.txt.bz2")]
+#[case("out.txt.xz")]
+#[case("out.txt.zst")]
+#[tokio::test]
+async fn test_async_create_compressed(#[case] name: &str) {
+    use tokio::io::AsyncWriteExt;
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.child(name);
+    let outfile = OutputArg::from_arg(path.path());
+    let mut writer = outfile.async_create_compressed().await.unwrap();
+    writer.write_all(TEXT.as_bytes()).await.unwrap();
+    writer.shutdown().await.unwrap();
+    let infile = InputArg::from_arg(path.path());
+    assert_eq!(infile.read_decompressed().unwrap(), TEXT.as_bytes());
+}
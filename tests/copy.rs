@@ -0,0 +1,72 @@
+#![cfg(test)]
+#![allow(missing_docs)]
+use assert_fs::TempDir;
+use assert_fs::prelude::*;
+use patharg::{InputArg, Operation, OutputArg};
+
+#[test]
+fn test_copy_file_to_file() {
+    let tmpdir = TempDir::new().unwrap();
+    let src = tmpdir.child("src.txt");
+    let contents = "Some text.\n".repeat(10000);
+    src.write_str(&contents).unwrap();
+    let dest = tmpdir.child("dest.txt");
+    dest.write_str("Old content\n").unwrap();
+    let n = patharg::copy(
+        &InputArg::from_arg(src.path()),
+        &OutputArg::from_arg(dest.path()),
+    )
+    .unwrap();
+    assert_eq!(n, u64::try_from(contents.len()).unwrap());
+    dest.assert(contents);
+}
+
+#[test]
+fn test_copy_empty_file() {
+    let tmpdir = TempDir::new().unwrap();
+    let src = tmpdir.child("src.txt");
+    src.touch().unwrap();
+    let dest = tmpdir.child("dest.txt");
+    let n = patharg::copy(
+        &InputArg::from_arg(src.path()),
+        &OutputArg::from_arg(dest.path()),
+    )
+    .unwrap();
+    assert_eq!(n, 0);
+    dest.assert("");
+}
+
+#[test]
+fn test_copy_nonexistent_input() {
+    let tmpdir = TempDir::new().unwrap();
+    let infile = InputArg::from_arg(tmpdir.child("nonexistent").path());
+    let dest = tmpdir.child("dest.txt");
+    let e = patharg::copy(&infile, &OutputArg::from_arg(dest.path())).unwrap_err();
+    assert_eq!(e.operation(), Operation::Open);
+    assert_eq!(e.input_arg(), Some(&infile));
+    dest.assert(predicates::path::missing());
+}
+
+#[test]
+fn test_copy_uncreatable_output() {
+    let tmpdir = TempDir::new().unwrap();
+    let src = tmpdir.child("src.txt");
+    src.write_str("Text\n").unwrap();
+    let outfile = OutputArg::from_arg(tmpdir.child("nonexistent").child("dest.txt").path());
+    let e = patharg::copy(&InputArg::from_arg(src.path()), &outfile).unwrap_err();
+    assert_eq!(e.operation(), Operation::Create);
+    assert_eq!(e.output_arg(), Some(&outfile));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_copy_write_error() {
+    let tmpdir = TempDir::new().unwrap();
+    let src = tmpdir.child("src.txt");
+    src.write_str("Text\n").unwrap();
+    let outfile = OutputArg::from_arg("/dev/full");
+    let e = patharg::copy(&InputArg::from_arg(src.path()), &outfile).unwrap_err();
+    assert_eq!(e.operation(), Operation::Copy);
+    assert_eq!(e.output_arg(), Some(&outfile));
+    assert!(e.to_string().starts_with("failed to copy to /dev/full: "));
+}
@@ -0,0 +1,88 @@
+#![cfg(test)]
+#![allow(missing_docs)]
+use assert_fs::TempDir;
+use assert_fs::prelude::*;
+use patharg::InputArg;
+
+#[test]
+fn test_read_to_string_lossy() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("input.txt");
+    tmpfile.write_binary(b"caf\xE9 ol\xC3\xA9\n").unwrap();
+    let infile = InputArg::from_arg(tmpfile.path());
+    assert!(infile.read_to_string().is_err());
+    assert_eq!(infile.read_to_string_lossy().unwrap(), "caf\u{FFFD} olé\n");
+}
+
+#[cfg(feature = "encoding")]
+mod decoded {
+    use super::*;
+    use patharg::Encoding;
+    use rstest::rstest;
+
+    fn utf16le(s: &str, bom: bool) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        if bom {
+            bytes.extend_from_slice(b"\xFF\xFE");
+        }
+        for unit in s.encode_utf16() {
+            bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+        bytes
+    }
+
+    fn utf16be(s: &str) -> Vec<u8> {
+        let mut bytes = b"\xFE\xFF".to_vec();
+        for unit in s.encode_utf16() {
+            bytes.extend_from_slice(&unit.to_be_bytes());
+        }
+        bytes
+    }
+
+    #[rstest]
+    #[case(b"Hello, w\xC3\xB6rld!\r\nLine 2\n".to_vec(), None)]
+    #[case(b"\xEF\xBB\xBFHello, w\xC3\xB6rld!\r\nLine 2\n".to_vec(), None)]
+    #[case(utf16le("Hello, wörld!\r\nLine 2\n", true), None)]
+    #[case(utf16be("Hello, wörld!\r\nLine 2\n"), None)]
+    #[case(
+        utf16le("Hello, wörld!\r\nLine 2\n", false),
+        Encoding::for_label(b"utf-16le")
+    )]
+    #[case(b"Hello, w\xF6rld!\r\nLine 2\n".to_vec(), Encoding::for_label(b"latin1"))]
+    // A BOM takes precedence over the given encoding:
+    #[case(
+        utf16le("Hello, wörld!\r\nLine 2\n", true),
+        Encoding::for_label(b"latin1")
+    )]
+    fn test_read_to_string_decoded(
+        #[case] data: Vec<u8>,
+        #[case] encoding: Option<&'static Encoding>,
+    ) {
+        let tmpdir = TempDir::new().unwrap();
+        let tmpfile = tmpdir.child("input.txt");
+        tmpfile.write_binary(&data).unwrap();
+        let infile = InputArg::from_arg(tmpfile.path());
+        assert_eq!(
+            infile.read_to_string_decoded(encoding).unwrap(),
+            "Hello, wörld!\r\nLine 2\n"
+        );
+        let lines = infile
+            .lines_decoded(encoding)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(lines, ["Hello, wörld!", "Line 2"]);
+    }
+
+    #[test]
+    fn test_read_to_string_decoded_invalid_utf8() {
+        let tmpdir = TempDir::new().unwrap();
+        let tmpfile = tmpdir.child("input.txt");
+        tmpfile.write_binary(b"caf\xE9\n").unwrap();
+        let infile = InputArg::from_arg(tmpfile.path());
+        assert_eq!(
+            infile.read_to_string_decoded(None).unwrap(),
+            "caf\u{FFFD}\n"
+        );
+    }
+}
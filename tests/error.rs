@@ -0,0 +1,72 @@
+#![cfg(test)]
+#![allow(missing_docs)]
+use assert_fs::TempDir;
+use assert_fs::prelude::*;
+use patharg::{InputArg, Operation, OutputArg};
+use std::io::ErrorKind;
+
+#[test]
+fn test_open_nonexistent() {
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.child("nonexistent.txt");
+    let infile = InputArg::from_arg(path.path());
+    let e = infile.open().unwrap_err();
+    assert_eq!(e.operation(), Operation::Open);
+    assert_eq!(e.kind(), ErrorKind::NotFound);
+    assert_eq!(e.input_arg(), Some(&infile));
+    assert_eq!(e.output_arg(), None);
+    assert_eq!(
+        e.to_string(),
+        format!("failed to open {}: {}", path.path().display(), e.io_error())
+    );
+}
+
+#[test]
+fn test_read_nonexistent() {
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.child("nonexistent.txt");
+    let infile = InputArg::from_arg(path.path());
+    let e = infile.read_to_string().unwrap_err();
+    assert_eq!(e.operation(), Operation::Read);
+    assert!(
+        e.to_string()
+            .starts_with(&format!("failed to read {}: ", path.path().display())),
+        "error message does not name the path: {e}"
+    );
+}
+
+#[test]
+fn test_create_in_nonexistent_dir() {
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.child("nonexistent").child("out.txt");
+    let outfile = OutputArg::from_arg(path.path());
+    let e = outfile.create().unwrap_err();
+    assert_eq!(e.operation(), Operation::Create);
+    assert_eq!(e.kind(), ErrorKind::NotFound);
+    assert_eq!(e.input_arg(), None);
+    assert_eq!(e.output_arg(), Some(&outfile));
+}
+
+#[test]
+fn test_into_io_error() {
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.child("nonexistent.txt");
+    let e = InputArg::from_arg(path.path()).open().unwrap_err();
+    let msg = e.to_string();
+    let ioerr = std::io::Error::from(e);
+    assert_eq!(ioerr.kind(), ErrorKind::NotFound);
+    assert_eq!(ioerr.to_string(), msg);
+}
+
+#[test]
+fn test_error_source() {
+    use std::error::Error as _;
+    let tmpdir = TempDir::new().unwrap();
+    let infile = InputArg::from_arg(tmpdir.child("nonexistent.txt").path());
+    let e = infile.open().unwrap_err();
+    let source = e
+        .source()
+        .and_then(|s| s.downcast_ref::<std::io::Error>())
+        .unwrap();
+    assert_eq!(source.kind(), ErrorKind::NotFound);
+}
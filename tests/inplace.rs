@@ -0,0 +1,94 @@
+#![cfg(test)]
+#![allow(missing_docs)]
+use assert_fs::TempDir;
+use assert_fs::prelude::*;
+use patharg::{InPlaceArg, Operation};
+use std::fs;
+use std::io::Write;
+
+fn entries(tmpdir: &TempDir) -> Vec<String> {
+    let mut names = fs::read_dir(tmpdir.path())
+        .unwrap()
+        .map(|r| r.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_in_place_commit() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("file.txt");
+    target.write_str("Old content\n").unwrap();
+    let arg = InPlaceArg::from_arg(target.path());
+    let content = arg.read_to_string().unwrap();
+    let mut f = arg.create().unwrap();
+    f.write_all(content.to_uppercase().as_bytes()).unwrap();
+    target.assert("Old content\n");
+    f.commit().unwrap();
+    target.assert("OLD CONTENT\n");
+    assert_eq!(entries(&tmpdir), ["file.txt"]);
+}
+
+#[test]
+fn test_in_place_drop_without_commit() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("file.txt");
+    target.write_str("Old content\n").unwrap();
+    let arg = InPlaceArg::from_arg(target.path());
+    let mut f = arg.create_with_backup(".bak").unwrap();
+    f.write_all(b"New content\n").unwrap();
+    drop(f);
+    target.assert("Old content\n");
+    assert_eq!(entries(&tmpdir), ["file.txt"]);
+}
+
+#[test]
+fn test_in_place_backup() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("file.txt");
+    target.write_str("Old content\n").unwrap();
+    let arg = InPlaceArg::from_arg(target.path());
+    let mut f = arg.create_with_backup(".bak").unwrap();
+    f.write_all(b"New content\n").unwrap();
+    f.commit().unwrap();
+    target.assert("New content\n");
+    tmpdir.child("file.txt.bak").assert("Old content\n");
+    assert_eq!(entries(&tmpdir), ["file.txt", "file.txt.bak"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_in_place_preserves_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("file.txt");
+    target.write_str("Old content\n").unwrap();
+    fs::set_permissions(target.path(), fs::Permissions::from_mode(0o640)).unwrap();
+    InPlaceArg::from_arg(target.path())
+        .write("New content\n")
+        .unwrap();
+    target.assert("New content\n");
+    let mode = fs::metadata(target.path()).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o640);
+}
+
+#[test]
+fn test_in_place_read_nonexistent() {
+    let tmpdir = TempDir::new().unwrap();
+    let arg = InPlaceArg::from_arg(tmpdir.child("missing.txt").path());
+    let e = arg.read().unwrap_err();
+    assert_eq!(e.operation(), Operation::Read);
+    assert_eq!(e.input_arg(), Some(&arg.input()));
+}
+
+#[test]
+fn test_stdio_args() {
+    let arg = InPlaceArg::from_arg("-");
+    assert!(arg.is_stdio());
+    assert_eq!(arg, InPlaceArg::default());
+    assert!(arg.input().is_stdin());
+    assert!(arg.output().is_stdout());
+    assert_eq!(arg.to_string(), "-");
+    assert_eq!(format!("{arg:#}"), "<stdin/stdout>");
+}
@@ -0,0 +1,96 @@
+#![cfg(test)]
+#![allow(missing_docs)]
+use assert_fs::TempDir;
+use assert_fs::prelude::*;
+use patharg::{InputArg, InputArgs, Operation};
+use std::io::{ErrorKind, Read};
+
+#[test]
+fn test_empty_is_stdin() {
+    let inputs = InputArgs::new(Vec::<String>::new());
+    assert_eq!(inputs.args(), [InputArg::Stdin]);
+    assert_eq!(inputs, InputArgs::default());
+}
+
+#[test]
+fn test_open_concatenates() {
+    let tmpdir = TempDir::new().unwrap();
+    let foo = tmpdir.child("foo.txt");
+    foo.write_str("Foo 1\nFoo 2").unwrap();
+    let empty = tmpdir.child("empty.txt");
+    empty.touch().unwrap();
+    let bar = tmpdir.child("bar.txt");
+    bar.write_str("Bar 1\n").unwrap();
+    let inputs = InputArgs::new([foo.path(), empty.path(), bar.path()]);
+    let mut s = String::new();
+    inputs.open().read_to_string(&mut s).unwrap();
+    assert_eq!(s, "Foo 1\nFoo 2Bar 1\n");
+}
+
+#[test]
+fn test_open_error_continues() {
+    let tmpdir = TempDir::new().unwrap();
+    let missing = tmpdir.child("missing.txt");
+    let bar = tmpdir.child("bar.txt");
+    bar.write_str("Bar 1\n").unwrap();
+    let inputs = InputArgs::new([missing.path(), bar.path()]);
+    let mut reader = inputs.open();
+    let mut s = String::new();
+    let e = reader.read_to_string(&mut s).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::NotFound);
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "Bar 1\n");
+}
+
+#[test]
+fn test_lines_track_location() {
+    let tmpdir = TempDir::new().unwrap();
+    let foo = tmpdir.child("foo.txt");
+    foo.write_str("Foo 1\nFoo 2").unwrap();
+    let bar = tmpdir.child("bar.txt");
+    bar.write_str("Bar 1\n").unwrap();
+    let inputs = InputArgs::new([foo.path(), bar.path()]);
+    let mut lines = inputs.lines();
+    let mut seen = Vec::new();
+    while let Some(r) = lines.next() {
+        let line = r.unwrap();
+        let arg = lines.current_arg().unwrap().clone();
+        seen.push((arg, lines.line_number(), line));
+    }
+    let foo_arg = InputArg::from_arg(foo.path());
+    let bar_arg = InputArg::from_arg(bar.path());
+    assert_eq!(
+        seen,
+        [
+            (foo_arg.clone(), 1, String::from("Foo 1")),
+            (foo_arg, 2, String::from("Foo 2")),
+            (bar_arg, 1, String::from("Bar 1")),
+        ]
+    );
+    assert_eq!(lines.current_arg(), None);
+}
+
+#[test]
+fn test_lines_error_continues() {
+    let tmpdir = TempDir::new().unwrap();
+    let missing = tmpdir.child("missing.txt");
+    let bar = tmpdir.child("bar.txt");
+    bar.write_str("Bar 1\n").unwrap();
+    let inputs = InputArgs::new([missing.path(), bar.path()]);
+    let mut lines = inputs.lines();
+    let e = lines.next().unwrap().unwrap_err();
+    assert_eq!(e.operation(), Operation::Open);
+    assert_eq!(e.input_arg(), Some(&InputArg::from_arg(missing.path())));
+    assert_eq!(lines.next().unwrap().unwrap(), "Bar 1");
+    assert!(lines.next().is_none(), "iterator yielded extra items");
+}
+
+#[test]
+fn test_validate() {
+    assert!(InputArgs::new(["-", "foo.txt"]).validate().is_ok());
+    let e = InputArgs::new(["-", "foo.txt", "-"])
+        .validate()
+        .unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::InvalidInput);
+    assert_eq!(e.input_arg(), Some(&InputArg::Stdin));
+}
@@ -15,6 +15,7 @@ build_test_binary_once!(revchars, "tests/bins");
 build_test_binary_once!(tokio_linelen, "tests/bins");
 build_test_binary_once!(tokio_revbytes, "tests/bins");
 build_test_binary_once!(tokio_revchars, "tests/bins");
+build_test_binary_once!(zcat, "tests/bins");
 
 #[allow(dead_code)]
 enum PathPolicy {
@@ -189,3 +190,12 @@ fn test_tokio_read_to_string_and_write(#[case] policy: IOPolicy) {
         ),
     );
 }
+
+#[apply(policies)]
+fn test_read_decompressed_and_write(#[case] policy: IOPolicy) {
+    policy.run(
+        path_to_zcat(),
+        &b"\x1F\x8B\x08\x08\x0B\xC1\xA0\x62\x00\x03\x68\x69\x2E\x74\x78\x74\x00\xF3\xC8\xE4\x02\x00\x9A\x3C\x22\xD5\x03\x00\x00\x00"[..],
+        &b"Hi\n"[..],
+    );
+}
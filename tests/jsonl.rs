@@ -0,0 +1,198 @@
+#![allow(missing_docs)]
+#![cfg(feature = "json")]
+use assert_fs::TempDir;
+use assert_fs::prelude::*;
+use patharg::{InputArg, Operation, OutputArg};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct Event {
+    level: String,
+    count: u32,
+}
+
+fn event(level: &str, count: u32) -> Event {
+    Event {
+        level: String::from(level),
+        count,
+    }
+}
+
+#[test]
+fn test_json_lines() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("events.jsonl");
+    tmpfile
+        .write_str(concat!(
+            "{\"level\": \"info\", \"count\": 1}\n",
+            "\n",
+            "{\"level\": \"warn\", \"count\": 2}\r\n",
+            "{\"level\": \"error\", \"count\": 3}",
+        ))
+        .unwrap();
+    let infile = InputArg::from_arg(tmpfile.path());
+    let mut records = infile.json_lines::<Event>().unwrap();
+    assert_eq!(records.input_arg(), &infile);
+    let events = records.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(
+        events,
+        [event("info", 1), event("warn", 2), event("error", 3)]
+    );
+    assert_eq!(records.line_number(), 4);
+}
+
+#[test]
+fn test_json_lines_invalid() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("events.jsonl");
+    tmpfile
+        .write_str(concat!(
+            "{\"level\": \"info\", \"count\": 1}\n",
+            "{\"level\": \"warn\"}\n",
+            "{\"level\": \"error\", \"count\": 3}\n",
+        ))
+        .unwrap();
+    let infile = InputArg::from_arg(tmpfile.path());
+    let mut records = infile.json_lines::<Event>().unwrap();
+    assert_eq!(records.next().unwrap().unwrap(), event("info", 1));
+    let e = records.next().unwrap().unwrap_err();
+    assert_eq!(e.operation(), Operation::Parse);
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(e.input_arg(), Some(&infile));
+    assert_eq!(e.line_number(), Some(2));
+    assert!(
+        e.to_string().starts_with(&format!(
+            "failed to parse {} at line 2: ",
+            tmpfile.path().display()
+        )),
+        "error message: {e}"
+    );
+    assert_eq!(records.next().unwrap().unwrap(), event("error", 3));
+    assert!(records.next().is_none());
+}
+
+#[test]
+fn test_json_lines_truncated() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("events.jsonl");
+    tmpfile
+        .write_str("{\"level\": \"info\", \"count\": 1\n")
+        .unwrap();
+    let infile = InputArg::from_arg(tmpfile.path());
+    let mut records = infile.json_lines::<Event>().unwrap();
+    let e = records.next().unwrap().unwrap_err();
+    assert_eq!(e.operation(), Operation::Parse);
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(e.line_number(), Some(1));
+}
+
+#[test]
+fn test_create_json_lines() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("events.jsonl");
+    let outfile = OutputArg::from_arg(tmpfile.path());
+    let mut out = outfile.create_json_lines().unwrap();
+    assert_eq!(out.output_arg(), &outfile);
+    out.write_record(&event("info", 1)).unwrap();
+    out.write_record(&event("warn", 2)).unwrap();
+    out.flush().unwrap();
+    drop(out);
+    tmpfile.assert("{\"level\":\"info\",\"count\":1}\n{\"level\":\"warn\",\"count\":2}\n");
+}
+
+#[test]
+fn test_create_json_lines_unserializable() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("events.jsonl");
+    let outfile = OutputArg::from_arg(tmpfile.path());
+    let mut out = outfile.create_json_lines().unwrap();
+    // JSON object keys must be strings, so this fails partway through.
+    let bad = BTreeMap::from([("a", BTreeMap::new()), ("b", BTreeMap::from([((1, 2), 3)]))]);
+    let e = out.write_record(&bad).unwrap_err();
+    assert_eq!(e.operation(), Operation::Write);
+    out.write_record(&event("info", 1)).unwrap();
+    out.flush().unwrap();
+    drop(out);
+    tmpfile.assert("{\"level\":\"info\",\"count\":1}\n");
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_async_json_lines() {
+    use tokio_stream::StreamExt;
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("events.jsonl");
+    tmpfile
+        .write_str(concat!(
+            "{\"level\": \"info\", \"count\": 1}\n",
+            "\n",
+            "not json\n",
+            "{\"level\": \"error\", \"count\": 3}\n",
+        ))
+        .unwrap();
+    let infile = InputArg::from_arg(tmpfile.path());
+    let mut records = infile.async_json_lines::<Event>().await.unwrap();
+    assert_eq!(records.next().await.unwrap().unwrap(), event("info", 1));
+    let e = records.next().await.unwrap().unwrap_err();
+    assert_eq!(e.operation(), Operation::Parse);
+    assert_eq!(e.line_number(), Some(3));
+    assert_eq!(records.next().await.unwrap().unwrap(), event("error", 3));
+    assert!(records.next().await.is_none());
+    assert_eq!(records.line_number(), 4);
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_async_create_json_lines() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("events.jsonl");
+    let outfile = OutputArg::from_arg(tmpfile.path());
+    let mut out = outfile.async_create_json_lines().await.unwrap();
+    out.write_record(&event("info", 1)).await.unwrap();
+    out.write_record(&event("warn", 2)).await.unwrap();
+    out.flush().await.unwrap();
+    drop(out);
+    tmpfile.assert("{\"level\":\"info\",\"count\":1}\n{\"level\":\"warn\",\"count\":2}\n");
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_async_create_json_lines_unserializable() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("events.jsonl");
+    let outfile = OutputArg::from_arg(tmpfile.path());
+    let mut out = outfile.async_create_json_lines().await.unwrap();
+    let bad = BTreeMap::from([("a", BTreeMap::new()), ("b", BTreeMap::from([((1, 2), 3)]))]);
+    let e = out.write_record(&bad).await.unwrap_err();
+    assert_eq!(e.operation(), Operation::Write);
+    out.write_record(&event("info", 1)).await.unwrap();
+    out.flush().await.unwrap();
+    drop(out);
+    tmpfile.assert("{\"level\":\"info\",\"count\":1}\n");
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_async_json_lines_sink() {
+    use futures_util::SinkExt;
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("events.jsonl");
+    let outfile = OutputArg::from_arg(tmpfile.path());
+    let mut out = outfile.async_create_json_lines().await.unwrap();
+    for i in 0..1000 {
+        out.send(event("debug", i)).await.unwrap();
+    }
+    out.close().await.unwrap();
+    drop(out);
+    let infile = InputArg::from_arg(tmpfile.path());
+    let events = infile
+        .json_lines::<Event>()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        events,
+        (0..1000).map(|i| event("debug", i)).collect::<Vec<_>>()
+    );
+}
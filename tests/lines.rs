@@ -0,0 +1,176 @@
+#![cfg(test)]
+#![allow(missing_docs)]
+use assert_fs::TempDir;
+use assert_fs::prelude::*;
+use patharg::{InputArg, LineTerminator};
+#[cfg(feature = "tokio")]
+use tokio_stream::StreamExt;
+
+#[test]
+fn test_byte_lines() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("input.txt");
+    tmpfile.write_binary(b"foo\nb\xE4r\r\n\nbaz\r").unwrap();
+    let infile = InputArg::from_arg(tmpfile.path());
+    let lines = infile
+        .byte_lines()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        lines,
+        [&b"foo"[..], &b"b\xE4r"[..], &b""[..], &b"baz\r"[..]]
+    );
+}
+
+#[test]
+fn test_split_nul() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("input.txt");
+    tmpfile
+        .write_binary(b"./foo\0./bar\nbaz\0./qu\xFFx\0")
+        .unwrap();
+    let infile = InputArg::from_arg(tmpfile.path());
+    let paths = infile
+        .split(b'\0')
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        paths,
+        [&b"./foo"[..], &b"./bar\nbaz"[..], &b"./qu\xFFx"[..]]
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_async_byte_lines() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("input.txt");
+    tmpfile.write_binary(b"foo\nb\xE4r\r\n\nbaz\r").unwrap();
+    let infile = InputArg::from_arg(tmpfile.path());
+    let lines = infile
+        .async_byte_lines()
+        .await
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .unwrap();
+    assert_eq!(
+        lines,
+        [&b"foo"[..], &b"b\xE4r"[..], &b""[..], &b"baz\r"[..]]
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_async_split_nul() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("input.txt");
+    tmpfile.write_binary(b"./foo\0./bar\nbaz\0").unwrap();
+    let infile = InputArg::from_arg(tmpfile.path());
+    let paths = infile
+        .async_split(b'\0')
+        .await
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .unwrap();
+    assert_eq!(paths, [&b"./foo"[..], &b"./bar\nbaz"[..]]);
+}
+
+#[test]
+fn test_lines_with_terminators() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("input.txt");
+    let text = "foo\nbar\r\n\r\n\nbaz";
+    tmpfile.write_str(text).unwrap();
+    let infile = InputArg::from_arg(tmpfile.path());
+    let lines = infile
+        .lines_with_terminators()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        lines
+            .iter()
+            .map(|ln| (ln.content(), ln.terminator()))
+            .collect::<Vec<_>>(),
+        [
+            ("foo", Some(LineTerminator::Lf)),
+            ("bar", Some(LineTerminator::CrLf)),
+            ("", Some(LineTerminator::CrLf)),
+            ("", Some(LineTerminator::Lf)),
+            ("baz", None),
+        ]
+    );
+    assert!(lines[1].is_crlf());
+    assert!(!lines[0].is_crlf());
+    assert_eq!(
+        lines.iter().map(ToString::to_string).collect::<String>(),
+        text
+    );
+    assert_eq!(lines[1].clone().into_content(), "bar");
+    assert_eq!(lines[1].clone().into_string(), "bar\r\n");
+}
+
+#[test]
+fn test_lines_with_terminators_lone_cr() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("input.txt");
+    tmpfile.write_str("foo\rbar\r").unwrap();
+    let infile = InputArg::from_arg(tmpfile.path());
+    let lines = infile
+        .lines_with_terminators()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].content(), "foo\rbar\r");
+    assert_eq!(lines[0].terminator(), None);
+}
+
+#[test]
+fn test_numbered_lines() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("input.txt");
+    tmpfile.write_str("foo\r\nbår\n\nbaz").unwrap();
+    let infile = InputArg::from_arg(tmpfile.path());
+    let mut lines = infile.numbered_lines().unwrap();
+    assert_eq!(lines.input_arg(), &infile);
+    assert_eq!(lines.line_number(), 0);
+    let items = lines.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(
+        items,
+        [
+            (1, 0, String::from("foo")),
+            (2, 5, String::from("bår")),
+            (3, 10, String::new()),
+            (4, 11, String::from("baz")),
+        ]
+    );
+    assert_eq!(lines.line_number(), 4);
+}
+
+#[test]
+fn test_numbered_lines_invalid_utf8() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("input.txt");
+    tmpfile.write_binary(b"foo\nb\xE4r\nbaz\n").unwrap();
+    let infile = InputArg::from_arg(tmpfile.path());
+    let mut lines = infile.numbered_lines().unwrap();
+    assert_eq!(lines.next().unwrap().unwrap(), (1, 0, String::from("foo")));
+    let e = lines.next().unwrap().unwrap_err();
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(e.input_arg(), Some(&infile));
+    assert_eq!(e.line_number(), Some(2));
+    assert!(
+        e.to_string().starts_with(&format!(
+            "failed to read {} at line 2: ",
+            tmpfile.path().display()
+        )),
+        "error message: {e}"
+    );
+    assert_eq!(lines.next().unwrap().unwrap(), (3, 8, String::from("baz")));
+    assert!(lines.next().is_none());
+}
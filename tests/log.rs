@@ -0,0 +1,80 @@
+#![cfg(test)]
+#![allow(missing_docs)]
+use assert_fs::TempDir;
+use assert_fs::prelude::*;
+use patharg::{LogArg, Operation, OutputArg};
+use std::ffi::OsString;
+use std::io::Write;
+use std::path::PathBuf;
+
+#[test]
+fn test_from_arg() {
+    assert_eq!(LogArg::from_arg("-"), LogArg::Stdout);
+    assert_eq!(LogArg::from_arg("/dev/stderr"), LogArg::Stderr);
+    assert_eq!(LogArg::from_arg("./-"), LogArg::Path(PathBuf::from("./-")));
+    assert_eq!(LogArg::default(), LogArg::Stderr);
+}
+
+#[test]
+fn test_from_arg_with_stderr_token() {
+    assert_eq!(
+        LogArg::from_arg_with_stderr_token("stderr", "stderr"),
+        LogArg::Stderr
+    );
+    assert_eq!(
+        LogArg::from_arg_with_stderr_token("-", "stderr"),
+        LogArg::Stdout
+    );
+    assert!(LogArg::from_arg_with_stderr_token("/dev/stderr", "stderr").is_path());
+}
+
+#[test]
+fn test_display_round_trip() {
+    for arg in [LogArg::Stdout, LogArg::Stderr, LogArg::from_arg("log.txt")] {
+        assert_eq!(arg.to_string().parse::<LogArg>().unwrap(), arg);
+        assert_eq!(LogArg::from_arg(OsString::from(arg.clone())), arg);
+    }
+    assert_eq!(format!("{:#}", LogArg::Stderr), "<stderr>");
+    assert_eq!(format!("{:#}", LogArg::Stdout), "<stdout>");
+}
+
+#[test]
+fn test_from_output_arg() {
+    assert_eq!(LogArg::from(OutputArg::Stdout), LogArg::Stdout);
+    assert_eq!(
+        LogArg::from(OutputArg::from_arg("log.txt")),
+        LogArg::Path(PathBuf::from("log.txt"))
+    );
+}
+
+#[test]
+fn test_create_and_append() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("log.txt");
+    target.write_str("Old content\n").unwrap();
+    let logfile = LogArg::from_arg(target.path());
+    let mut f = logfile.append().unwrap();
+    f.write_all(b"Appended\n").unwrap();
+    drop(f);
+    target.assert("Old content\nAppended\n");
+    let mut f = logfile.create().unwrap();
+    f.write_all(b"New content\n").unwrap();
+    drop(f);
+    target.assert("New content\n");
+}
+
+#[test]
+fn test_write_stderr() {
+    LogArg::Stderr.write("").unwrap();
+}
+
+#[test]
+fn test_create_error() {
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.child("nonexistent").child("log.txt");
+    let logfile = LogArg::from_arg(path.path());
+    let e = logfile.create().unwrap_err();
+    assert_eq!(e.operation(), Operation::Create);
+    assert_eq!(e.log_arg(), Some(&logfile));
+    assert_eq!(e.output_arg(), None);
+}
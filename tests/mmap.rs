@@ -0,0 +1,49 @@
+#![allow(missing_docs)]
+#![allow(unsafe_code)]
+#![cfg(feature = "mmap")]
+use assert_fs::TempDir;
+use assert_fs::prelude::*;
+use patharg::{InputArg, Operation};
+
+#[test]
+fn test_read_mapped_file() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("data.bin");
+    tmpfile.write_binary(b"Hello, world!\n").unwrap();
+    let infile = InputArg::from_arg(tmpfile.path());
+    // SAFETY: Nothing modifies the file during the test.
+    let input = unsafe { infile.read_mapped() }.unwrap();
+    assert!(input.is_mapped());
+    assert_eq!(&*input, b"Hello, world!\n");
+}
+
+#[test]
+fn test_read_mapped_empty_file() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("empty.bin");
+    tmpfile.touch().unwrap();
+    let infile = InputArg::from_arg(tmpfile.path());
+    // SAFETY: Nothing modifies the file during the test.
+    let input = unsafe { infile.read_mapped() }.unwrap();
+    assert!(input.is_empty());
+}
+
+#[test]
+fn test_read_mapped_nonexistent() {
+    let tmpdir = TempDir::new().unwrap();
+    let infile = InputArg::from_arg(tmpdir.child("nonexistent").path());
+    // SAFETY: The file does not exist.
+    let e = unsafe { infile.read_mapped() }.unwrap_err();
+    assert_eq!(e.operation(), Operation::Open);
+    assert_eq!(e.input_arg(), Some(&infile));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_read_mapped_special_file() {
+    let infile = InputArg::from_arg("/dev/null");
+    // SAFETY: Nothing modifies the file during the test.
+    let input = unsafe { infile.read_mapped() }.unwrap();
+    assert!(!input.is_mapped());
+    assert!(input.is_empty());
+}
@@ -0,0 +1,412 @@
+#![cfg(test)]
+#![allow(missing_docs)]
+use assert_fs::TempDir;
+use assert_fs::prelude::*;
+use patharg::{BackupPolicy, InputArg, Operation, OutputArg};
+use std::fs;
+use std::io::Write;
+
+fn entries(tmpdir: &TempDir) -> Vec<String> {
+    let mut names = fs::read_dir(tmpdir.path())
+        .unwrap()
+        .map(|r| r.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_create_atomic_commit() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("out.txt");
+    target.write_str("Old content\n").unwrap();
+    let outfile = OutputArg::from_arg(target.path());
+    let mut f = outfile.create_atomic().unwrap();
+    f.write_all(b"New content\n").unwrap();
+    target.assert("Old content\n");
+    f.commit().unwrap();
+    target.assert("New content\n");
+    assert_eq!(entries(&tmpdir), ["out.txt"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_create_atomic_preserves_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("file.txt");
+    target.write_str("Old content\n").unwrap();
+    fs::set_permissions(target.path(), fs::Permissions::from_mode(0o644)).unwrap();
+    let mut f = OutputArg::from_arg(target.path()).create_atomic().unwrap();
+    writeln!(f, "New content").unwrap();
+    f.commit().unwrap();
+    target.assert("New content\n");
+    let mode = fs::metadata(target.path()).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o644);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_create_atomic_new_file_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+    let tmpdir = TempDir::new().unwrap();
+    let plain = tmpdir.child("plain.txt");
+    OutputArg::from_arg(plain.path()).create().unwrap();
+    let target = tmpdir.child("atomic.txt");
+    let f = OutputArg::from_arg(target.path()).create_atomic().unwrap();
+    f.commit().unwrap();
+    let plain_mode = fs::metadata(plain.path()).unwrap().permissions().mode();
+    let mode = fs::metadata(target.path()).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, plain_mode & 0o777);
+}
+
+#[test]
+fn test_create_atomic_commit_new_file() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("out.txt");
+    let outfile = OutputArg::from_arg(target.path());
+    let mut f = outfile.create_atomic().unwrap();
+    f.write_all(b"New content\n").unwrap();
+    f.commit().unwrap();
+    target.assert("New content\n");
+    assert_eq!(entries(&tmpdir), ["out.txt"]);
+}
+
+#[test]
+fn test_create_atomic_drop_without_commit() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("out.txt");
+    target.write_str("Old content\n").unwrap();
+    let outfile = OutputArg::from_arg(target.path());
+    let mut f = outfile.create_atomic().unwrap();
+    f.write_all(b"New content\n").unwrap();
+    drop(f);
+    target.assert("Old content\n");
+    assert_eq!(entries(&tmpdir), ["out.txt"]);
+}
+
+#[test]
+fn test_append() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("log.txt");
+    target.write_str("First line\n").unwrap();
+    let outfile = OutputArg::from_arg(target.path());
+    let mut f = outfile.append().unwrap();
+    f.write_all(b"Second line\n").unwrap();
+    drop(f);
+    target.assert("First line\nSecond line\n");
+}
+
+#[test]
+fn test_write_append_creates_file() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("log.txt");
+    let outfile = OutputArg::from_arg(target.path());
+    outfile.write_append("First line\n").unwrap();
+    outfile.write_append("Second line\n").unwrap();
+    target.assert("First line\nSecond line\n");
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_async_write_append() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("log.txt");
+    target.write_str("First line\n").unwrap();
+    let outfile = OutputArg::from_arg(target.path());
+    outfile.async_write_append("Second line\n").await.unwrap();
+    target.assert("First line\nSecond line\n");
+}
+
+#[test]
+fn test_create_new() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("out.txt");
+    let outfile = OutputArg::from_arg(target.path());
+    let mut f = outfile.create_new().unwrap();
+    f.write_all(b"New content\n").unwrap();
+    drop(f);
+    target.assert("New content\n");
+}
+
+#[test]
+fn test_create_new_existing() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("out.txt");
+    target.write_str("Old content\n").unwrap();
+    let outfile = OutputArg::from_arg(target.path());
+    let e = outfile.create_new().unwrap_err();
+    assert_eq!(e.kind(), std::io::ErrorKind::AlreadyExists);
+    assert_eq!(e.output_arg(), Some(&outfile));
+    assert!(
+        e.to_string()
+            .starts_with(&format!("failed to open {}: ", target.path().display())),
+        "error message does not name the path: {e}"
+    );
+    target.assert("Old content\n");
+}
+
+#[test]
+fn test_create_with_clobber() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("out.txt");
+    target.write_str("Old content\n").unwrap();
+    let outfile = OutputArg::from_arg(target.path());
+    assert!(outfile.create_with_clobber(false).is_err());
+    target.assert("Old content\n");
+    let mut f = outfile.create_with_clobber(true).unwrap();
+    f.write_all(b"New content\n").unwrap();
+    drop(f);
+    target.assert("New content\n");
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_async_create_new_existing() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("out.txt");
+    target.write_str("Old content\n").unwrap();
+    let outfile = OutputArg::from_arg(target.path());
+    let e = outfile.async_create_new().await.unwrap_err();
+    assert_eq!(e.kind(), std::io::ErrorKind::AlreadyExists);
+    target.assert("Old content\n");
+}
+
+#[test]
+fn test_options_no_truncate() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("out.txt");
+    target.write_str("Old content\n").unwrap();
+    let outfile = OutputArg::from_arg(target.path());
+    let mut f = OutputArg::options().truncate(false).open(&outfile).unwrap();
+    f.write_all(b"New").unwrap();
+    drop(f);
+    target.assert("New content\n");
+}
+
+#[test]
+fn test_options_create_new_existing() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("out.txt");
+    target.write_str("Old content\n").unwrap();
+    let outfile = OutputArg::from_arg(target.path());
+    let e = OutputArg::options()
+        .create_new(true)
+        .open(&outfile)
+        .unwrap_err();
+    assert_eq!(e.kind(), std::io::ErrorKind::AlreadyExists);
+    target.assert("Old content\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_options_mode() {
+    use std::os::unix::fs::PermissionsExt;
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("out.txt");
+    let outfile = OutputArg::from_arg(target.path());
+    let f = OutputArg::options().mode(0o600).open(&outfile).unwrap();
+    drop(f);
+    let mode = fs::metadata(target.path()).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
+#[test]
+fn test_conflicts_with() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("foo.txt");
+    target.write_str("Content\n").unwrap();
+    let other = tmpdir.child("bar.txt");
+    other.write_str("Content\n").unwrap();
+    let infile = InputArg::from_arg(target.path());
+    let same = OutputArg::from_arg(tmpdir.path().join(".").join("foo.txt"));
+    assert!(same.conflicts_with(&infile).unwrap());
+    let different = OutputArg::from_arg(other.path());
+    assert!(!different.conflicts_with(&infile).unwrap());
+    let missing = OutputArg::from_arg(tmpdir.child("missing.txt").path());
+    assert!(!missing.conflicts_with(&infile).unwrap());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_conflicts_with_symlink() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("foo.txt");
+    target.write_str("Content\n").unwrap();
+    let link = tmpdir.child("link.txt");
+    link.symlink_to_file(target.path()).unwrap();
+    let infile = InputArg::from_arg(target.path());
+    let outfile = OutputArg::from_arg(link.path());
+    assert!(outfile.conflicts_with(&infile).unwrap());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_conflicts_with_fifo() {
+    let tmpdir = TempDir::new().unwrap();
+    let fifo = tmpdir.child("fifo");
+    let status = std::process::Command::new("mkfifo")
+        .arg(fifo.path())
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let infile = InputArg::from_arg(fifo.path());
+    let outfile = OutputArg::from_arg(fifo.path());
+    // This would block forever if the FIFO were opened.
+    assert!(!outfile.conflicts_with(&infile).unwrap());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_conflicts_with_write_only() {
+    use std::os::unix::fs::PermissionsExt;
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("foo.txt");
+    target.write_str("Content\n").unwrap();
+    fs::set_permissions(target.path(), fs::Permissions::from_mode(0o200)).unwrap();
+    let infile = InputArg::from_arg(target.path());
+    let outfile = OutputArg::from_arg(target.path());
+    assert!(outfile.conflicts_with(&infile).unwrap());
+}
+
+#[test]
+fn test_options_distinct_from() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("foo.txt");
+    target.write_str("Content\n").unwrap();
+    let infile = InputArg::from_arg(target.path());
+    let outfile = OutputArg::from_arg(target.path());
+    let e = OutputArg::options()
+        .distinct_from(&infile)
+        .open(&outfile)
+        .unwrap_err();
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(e.output_arg(), Some(&outfile));
+    target.assert("Content\n");
+    let other = OutputArg::from_arg(tmpdir.child("bar.txt").path());
+    let mut f = OutputArg::options()
+        .distinct_from(&infile)
+        .open(&other)
+        .unwrap();
+    f.write_all(b"New content\n").unwrap();
+}
+
+#[test]
+fn test_create_with_simple_backup() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("out.txt");
+    target.write_str("Old content\n").unwrap();
+    let outfile = OutputArg::from_arg(target.path());
+    let mut f = outfile.create_with_backup(BackupPolicy::Simple).unwrap();
+    f.write_all(b"New content\n").unwrap();
+    drop(f);
+    target.assert("New content\n");
+    tmpdir.child("out.txt~").assert("Old content\n");
+    assert_eq!(entries(&tmpdir), ["out.txt", "out.txt~"]);
+}
+
+#[test]
+fn test_create_with_numbered_backup() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("out.txt");
+    let outfile = OutputArg::from_arg(target.path());
+    for i in 1..=3 {
+        outfile
+            .create_with_backup(BackupPolicy::Numbered)
+            .unwrap()
+            .write_all(format!("Version {i}\n").as_bytes())
+            .unwrap();
+    }
+    target.assert("Version 3\n");
+    tmpdir.child("out.txt.~1~").assert("Version 1\n");
+    tmpdir.child("out.txt.~2~").assert("Version 2\n");
+    assert_eq!(entries(&tmpdir), ["out.txt", "out.txt.~1~", "out.txt.~2~"]);
+}
+
+#[test]
+fn test_create_with_existing_backup() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("out.txt");
+    target.write_str("Old content\n").unwrap();
+    let outfile = OutputArg::from_arg(target.path());
+    outfile.create_with_backup(BackupPolicy::Existing).unwrap();
+    assert_eq!(entries(&tmpdir), ["out.txt", "out.txt~"]);
+    tmpdir.child("out.txt.~4~").touch().unwrap();
+    outfile.create_with_backup(BackupPolicy::Existing).unwrap();
+    assert_eq!(
+        entries(&tmpdir),
+        ["out.txt", "out.txt.~4~", "out.txt.~5~", "out.txt~"]
+    );
+}
+
+#[test]
+fn test_options_backup_suffix() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("out.txt");
+    target.write_str("Old content\n").unwrap();
+    let outfile = OutputArg::from_arg(target.path());
+    OutputArg::options()
+        .backup(BackupPolicy::Simple)
+        .backup_suffix(".orig")
+        .open(&outfile)
+        .unwrap();
+    tmpdir.child("out.txt.orig").assert("Old content\n");
+}
+
+#[test]
+fn test_options_backup_restored_on_open_error() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("out.txt");
+    target.write_str("Old content\n").unwrap();
+    let outfile = OutputArg::from_arg(target.path());
+    // The backup moves the file away, so opening without `create` fails.
+    let e = OutputArg::options()
+        .create(false)
+        .backup(BackupPolicy::Simple)
+        .open(&outfile)
+        .unwrap_err();
+    assert_eq!(e.operation(), Operation::Open);
+    assert_eq!(e.kind(), std::io::ErrorKind::NotFound);
+    target.assert("Old content\n");
+    assert_eq!(entries(&tmpdir), ["out.txt"]);
+}
+
+#[test]
+fn test_create_with_backup_error_operation() {
+    let tmpdir = TempDir::new().unwrap();
+    let outfile = OutputArg::from_arg(tmpdir.child("nonexistent").child("out.txt").path());
+    let e = outfile.create().unwrap_err();
+    assert_eq!(e.operation(), Operation::Create);
+    let e = outfile
+        .create_with_backup(BackupPolicy::Simple)
+        .unwrap_err();
+    assert_eq!(e.operation(), Operation::Create);
+}
+
+#[test]
+fn test_options_backup_create_new() {
+    let tmpdir = TempDir::new().unwrap();
+    let target = tmpdir.child("out.txt");
+    target.write_str("Old content\n").unwrap();
+    let outfile = OutputArg::from_arg(target.path());
+    let e = OutputArg::options()
+        .create_new(true)
+        .backup(BackupPolicy::Simple)
+        .open(&outfile)
+        .unwrap_err();
+    assert_eq!(e.kind(), std::io::ErrorKind::AlreadyExists);
+    target.assert("Old content\n");
+    tmpdir.child("out.txt~").assert(predicates::path::missing());
+}
+
+#[test]
+fn test_parse_backup_policy() {
+    assert_eq!("t".parse::<BackupPolicy>(), Ok(BackupPolicy::Numbered));
+    assert_eq!("never".parse::<BackupPolicy>(), Ok(BackupPolicy::Simple));
+    assert_eq!("nil".parse::<BackupPolicy>(), Ok(BackupPolicy::Existing));
+    assert_eq!("off".parse::<BackupPolicy>(), Ok(BackupPolicy::None));
+    assert!(
+        "always".parse::<BackupPolicy>().is_err(),
+        "parsed invalid policy"
+    );
+}
@@ -0,0 +1,220 @@
+#![cfg(test)]
+#![allow(missing_docs)]
+use patharg::{ArgParser, InPlaceArg, InputArg, LogArg, OutputArg};
+use std::path::PathBuf;
+
+#[test]
+fn test_default_matches_from_arg() {
+    let parser = ArgParser::default();
+    for arg in [
+        "-",
+        "./-",
+        "foo.txt",
+        "/dev/stdin",
+        "/dev/stdout",
+        "/dev/fd/0",
+    ] {
+        assert_eq!(parser.parse_input(arg), InputArg::from_arg(arg));
+        assert_eq!(parser.parse_output(arg), OutputArg::from_arg(arg));
+        assert_eq!(parser.parse_in_place(arg), InPlaceArg::from_arg(arg));
+        assert_eq!(parser.parse_log(arg), LogArg::from_arg(arg));
+    }
+}
+
+#[test]
+fn test_custom_sentinel() {
+    let mut parser = ArgParser::new();
+    parser.sentinel("STDIO");
+    assert_eq!(parser.parse_input("STDIO"), InputArg::Stdin);
+    assert_eq!(parser.parse_output("STDIO"), OutputArg::Stdout);
+    assert_eq!(parser.parse_in_place("STDIO"), InPlaceArg::Stdio);
+    assert_eq!(parser.parse_log("STDIO"), LogArg::Stdout);
+    assert_eq!(parser.parse_input("-"), InputArg::Path(PathBuf::from("-")));
+}
+
+#[test]
+fn test_no_sentinel() {
+    let mut parser = ArgParser::new();
+    parser.no_sentinel();
+    assert_eq!(parser.parse_input("-"), InputArg::Path(PathBuf::from("-")));
+    assert_eq!(
+        parser.parse_output("-"),
+        OutputArg::Path(PathBuf::from("-"))
+    );
+}
+
+#[test]
+fn test_dev_paths() {
+    let mut parser = ArgParser::new();
+    parser.dev_paths(true);
+    assert_eq!(parser.parse_input("-"), InputArg::Stdin);
+    assert_eq!(parser.parse_input("/dev/stdin"), InputArg::Stdin);
+    assert_eq!(parser.parse_input("/dev/fd/0"), InputArg::Stdin);
+    assert!(parser.parse_input("/dev/stdout").is_path());
+    assert!(parser.parse_input("/dev/fd/1").is_path());
+    assert_eq!(parser.parse_output("/dev/stdout"), OutputArg::Stdout);
+    assert_eq!(parser.parse_output("/dev/fd/1"), OutputArg::Stdout);
+    assert!(parser.parse_output("/dev/stdin").is_path());
+    assert_eq!(parser.parse_log("/dev/fd/1"), LogArg::Stdout);
+    assert_eq!(parser.parse_log("/dev/fd/2"), LogArg::Stderr);
+    assert!(parser.parse_in_place("/dev/stdin").is_path());
+}
+
+#[cfg(unix)]
+#[test]
+#[allow(unsafe_code)]
+fn test_fd_args() {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+    use std::os::fd::AsRawFd;
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.child("input.txt");
+    path.write_str("Read via fd\n").unwrap();
+    let f = std::fs::File::open(path.path()).unwrap();
+    let mut parser = ArgParser::new();
+    // SAFETY: The only descriptors named below are `f` and the standard
+    // streams, all of which stay open for the rest of the test.
+    unsafe { parser.fd_args(true) };
+    let arg = format!("fd:{}", f.as_raw_fd());
+    let infile = parser.parse_input(&arg);
+    assert!(matches!(infile, InputArg::Fd(fd) if fd.as_raw_fd() == f.as_raw_fd()));
+    assert!(!infile.is_path());
+    assert_eq!(infile.to_string(), arg);
+    assert_eq!(infile.read_to_string().unwrap(), "Read via fd\n");
+    let dev_arg = format!("/dev/fd/{}", f.as_raw_fd());
+    assert_eq!(parser.parse_input(&dev_arg), infile);
+    for arg in [
+        "fd:x",
+        "fd:",
+        "fd:+3",
+        "fd:-3",
+        "fd: 3",
+        "fd:99999999999",
+        "/dev/fd/",
+        "/dev/fd/x",
+        "/dev/fd/+3",
+    ] {
+        assert_eq!(parser.parse_input(arg), InputArg::Path(PathBuf::from(arg)));
+    }
+    assert!(matches!(parser.parse_output("fd:1"), OutputArg::Fd(_)));
+    assert!(matches!(parser.parse_log("fd:2"), LogArg::Fd(_)));
+    parser.dev_paths(true);
+    assert_eq!(parser.parse_input("fd:0"), InputArg::Stdin);
+    assert_eq!(parser.parse_output("fd:1"), OutputArg::Stdout);
+    assert_eq!(parser.parse_log("fd:2"), LogArg::Stderr);
+}
+
+#[cfg(unix)]
+#[test]
+#[allow(unsafe_code)]
+fn test_fd_args_read_from_offset() {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+    use std::io::{Read, Seek};
+    use std::os::fd::AsRawFd;
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.child("input.txt");
+    path.write_str("First line\nSecond line\n").unwrap();
+    let mut f = std::fs::File::open(path.path()).unwrap();
+    let mut buf = [0; 11];
+    f.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"First line\n");
+    let mut parser = ArgParser::new();
+    // SAFETY: `f` stays open for the rest of the test.
+    unsafe { parser.fd_args(true) };
+    let infile = parser.parse_input(format!("fd:{}", f.as_raw_fd()));
+    assert_eq!(infile.read_to_string().unwrap(), "Second line\n");
+    assert_eq!(f.stream_position().unwrap(), 23);
+}
+
+#[cfg(unix)]
+#[test]
+#[allow(unsafe_code)]
+fn test_fd_args_read_pipe() {
+    use std::io::Write;
+    use std::os::fd::AsRawFd;
+    let (reader, mut writer) = std::io::pipe().unwrap();
+    writer.write_all(b"Read via pipe\n").unwrap();
+    drop(writer);
+    let mut parser = ArgParser::new();
+    // SAFETY: `reader` stays open for the rest of the test.
+    unsafe { parser.fd_args(true) };
+    let infile = parser.parse_input(format!("fd:{}", reader.as_raw_fd()));
+    assert_eq!(infile.read_to_string().unwrap(), "Read via pipe\n");
+}
+
+#[cfg(unix)]
+#[test]
+#[allow(unsafe_code)]
+fn test_fd_args_write_append() {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+    use std::io::Write;
+    use std::os::fd::AsRawFd;
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.child("output.txt");
+    path.write_str("Existing line\n").unwrap();
+    let f = std::fs::OpenOptions::new()
+        .append(true)
+        .open(path.path())
+        .unwrap();
+    let mut parser = ArgParser::new();
+    // SAFETY: `f` stays open for the rest of the test.
+    unsafe { parser.fd_args(true) };
+    let outfile = parser.parse_output(format!("fd:{}", f.as_raw_fd()));
+    let mut out = outfile.create().unwrap();
+    writeln!(out, "Appended line").unwrap();
+    drop(out);
+    path.assert("Existing line\nAppended line\n");
+}
+
+#[cfg(unix)]
+#[test]
+#[allow(unsafe_code)]
+fn test_fd_args_dev_fd_path_does_not_truncate() {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+    use std::io::Write;
+    use std::os::fd::AsRawFd;
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.child("output.txt");
+    path.write_str("Existing line\n").unwrap();
+    let f = std::fs::OpenOptions::new()
+        .append(true)
+        .open(path.path())
+        .unwrap();
+    let mut parser = ArgParser::new();
+    // SAFETY: `f` stays open for the rest of the test.
+    unsafe { parser.fd_args(true) };
+    let outfile = parser.parse_output(format!("/dev/fd/{}", f.as_raw_fd()));
+    assert!(matches!(outfile, OutputArg::Fd(fd) if fd.as_raw_fd() == f.as_raw_fd()));
+    let mut out = outfile.create().unwrap();
+    writeln!(out, "Appended line").unwrap();
+    drop(out);
+    path.assert("Existing line\nAppended line\n");
+}
+
+#[cfg(unix)]
+#[test]
+#[allow(unsafe_code)]
+fn test_fd_args_write_at_offset() {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+    use std::io::{Seek, SeekFrom};
+    use std::os::fd::AsRawFd;
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.child("output.txt");
+    path.write_str("0123456789\n").unwrap();
+    let mut f = std::fs::OpenOptions::new()
+        .write(true)
+        .open(path.path())
+        .unwrap();
+    f.seek(SeekFrom::Start(4)).unwrap();
+    let mut parser = ArgParser::new();
+    // SAFETY: `f` stays open for the rest of the test.
+    unsafe { parser.fd_args(true) };
+    let outfile = parser.parse_output(format!("fd:{}", f.as_raw_fd()));
+    outfile.write("abc").unwrap();
+    path.assert("0123abc789\n");
+    assert_eq!(f.stream_position().unwrap(), 7);
+}
@@ -0,0 +1,94 @@
+#![cfg(test)]
+#![allow(missing_docs)]
+use assert_fs::TempDir;
+use assert_fs::prelude::*;
+use patharg::InputArg;
+use std::io::{Read, Seek, SeekFrom};
+
+#[test]
+fn test_open_seekable_file() {
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("data.bin");
+    tmpfile.write_binary(b"0123456789").unwrap();
+    let infile = InputArg::from_arg(tmpfile.path());
+    let mut f = infile.open_seekable().unwrap();
+    assert!(f.is_right());
+    f.seek(SeekFrom::End(-3)).unwrap();
+    let mut buf = String::new();
+    f.read_to_string(&mut buf).unwrap();
+    assert_eq!(buf, "789");
+    f.seek(SeekFrom::Start(2)).unwrap();
+    let mut buf = [0; 2];
+    f.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"23");
+}
+
+#[test]
+fn test_open_seekable_nonexistent() {
+    let tmpdir = TempDir::new().unwrap();
+    let infile = InputArg::from_arg(tmpdir.child("nonexistent").path());
+    let e = infile.open_seekable().unwrap_err();
+    assert_eq!(e.operation(), patharg::Operation::Open);
+    assert_eq!(e.input_arg(), Some(&infile));
+}
+
+#[cfg(unix)]
+mod fifo {
+    use super::*;
+    use rstest::rstest;
+    use std::fs;
+    use std::io::Write;
+    use std::process::Command;
+    use std::thread;
+
+    #[rstest]
+    #[case(patharg::DEFAULT_SPOOL_THRESHOLD, true)]
+    #[case(4, false)]
+    #[case(0, false)]
+    fn test_open_seekable_fifo(#[case] threshold: usize, #[case] in_memory: bool) {
+        let tmpdir = TempDir::new().unwrap();
+        let fifo = tmpdir.child("fifo");
+        let status = Command::new("mkfifo").arg(fifo.path()).status().unwrap();
+        assert!(status.success(), "mkfifo failed");
+        let path = fifo.path().to_path_buf();
+        let writer = thread::spawn(move || {
+            let mut fp = fs::OpenOptions::new().write(true).open(path).unwrap();
+            fp.write_all(b"0123456789").unwrap();
+        });
+        let infile = InputArg::from_arg(fifo.path());
+        let mut f = infile.open_seekable_with_threshold(threshold).unwrap();
+        writer.join().unwrap();
+        assert_eq!(f.is_left(), in_memory);
+        f.seek(SeekFrom::End(-3)).unwrap();
+        let mut buf = String::new();
+        f.read_to_string(&mut buf).unwrap();
+        assert_eq!(buf, "789");
+        f.rewind().unwrap();
+        let mut buf = String::new();
+        f.read_to_string(&mut buf).unwrap();
+        assert_eq!(buf, "0123456789");
+    }
+}
+
+#[cfg(unix)]
+#[test]
+#[allow(unsafe_code)]
+fn test_open_seekable_fd_at_offset() {
+    use patharg::FdArg;
+    use std::os::fd::{AsRawFd, FromRawFd};
+    let tmpdir = TempDir::new().unwrap();
+    let tmpfile = tmpdir.child("data.txt");
+    tmpfile.write_str("First line\nSecond line\n").unwrap();
+    let mut fp = std::fs::File::open(tmpfile.path()).unwrap();
+    fp.seek(SeekFrom::Start(11)).unwrap();
+    // SAFETY: `fp` stays open for the rest of the test.
+    let infile = InputArg::Fd(unsafe { FdArg::from_raw_fd(fp.as_raw_fd()) });
+    let mut f = infile.open_seekable().unwrap();
+    let mut buf = String::new();
+    f.read_to_string(&mut buf).unwrap();
+    assert_eq!(buf, "Second line\n");
+    f.seek(SeekFrom::Start(0)).unwrap();
+    let mut buf = String::new();
+    f.read_to_string(&mut buf).unwrap();
+    assert_eq!(buf, "Second line\n");
+}
@@ -0,0 +1,43 @@
+#![cfg(test)]
+#![allow(missing_docs)]
+use assert_cmd::Command;
+use assert_fs::TempDir;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use test_binary::build_test_binary_once;
+
+build_test_binary_once!(cat, "tests/bins");
+
+fn run_cat(policy: &str) -> assert_cmd::assert::Assert {
+    let tmpdir = TempDir::new().unwrap();
+    let file = tmpdir.child("file.txt");
+    file.write_str("From file\n").unwrap();
+    Command::new(path_to_cat())
+        .arg("--stdin-policy")
+        .arg(policy)
+        .arg("-")
+        .arg(file.path())
+        .arg("-")
+        .write_stdin("From stdin\n")
+        .assert()
+}
+
+#[test]
+fn test_allow_stdin_reuse() {
+    run_cat("allow").success().stdout("From stdin\nFrom file\n");
+}
+
+#[test]
+fn test_deny_stdin_reuse() {
+    run_cat("deny")
+        .failure()
+        .stdout("From stdin\nFrom file\n")
+        .stderr(predicate::str::contains(
+            "standard input has already been read",
+        ));
+}
+
+#[test]
+fn test_empty_on_stdin_reuse() {
+    run_cat("empty").success().stdout("From stdin\nFrom file\n");
+}
@@ -0,0 +1,155 @@
+#![allow(missing_docs)]
+#![cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+use assert_fs::TempDir;
+use assert_fs::prelude::*;
+use patharg::{InputArg, Operation};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct Config {
+    name: String,
+    count: u32,
+}
+
+fn config() -> Config {
+    Config {
+        name: String::from("widget"),
+        count: 42,
+    }
+}
+
+#[cfg(feature = "json")]
+mod json {
+    use super::*;
+    use patharg::OutputArg;
+
+    #[test]
+    fn test_read_json() {
+        let tmpdir = TempDir::new().unwrap();
+        let tmpfile = tmpdir.child("config.json");
+        tmpfile
+            .write_str(r#"{"name": "widget", "count": 42}"#)
+            .unwrap();
+        let infile = InputArg::from_arg(tmpfile.path());
+        assert_eq!(infile.read_json::<Config>().unwrap(), config());
+    }
+
+    #[test]
+    fn test_read_json_invalid() {
+        let tmpdir = TempDir::new().unwrap();
+        let tmpfile = tmpdir.child("config.json");
+        tmpfile
+            .write_str("{\"name\": \"widget\",\n\"count\": \"many\"}")
+            .unwrap();
+        let infile = InputArg::from_arg(tmpfile.path());
+        let e = infile.read_json::<Config>().unwrap_err();
+        assert_eq!(e.operation(), Operation::Parse);
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(e.input_arg(), Some(&infile));
+        let msg = e.to_string();
+        assert!(
+            msg.starts_with(&format!("failed to parse {}: ", tmpfile.path().display())),
+            "error message: {msg}"
+        );
+        assert!(msg.contains("line 2"), "error message: {msg}");
+    }
+
+    #[test]
+    fn test_read_json_truncated() {
+        let tmpdir = TempDir::new().unwrap();
+        let tmpfile = tmpdir.child("config.json");
+        tmpfile.write_str("{\"name\": \"widget\",").unwrap();
+        let infile = InputArg::from_arg(tmpfile.path());
+        let e = infile.read_json::<Config>().unwrap_err();
+        assert_eq!(e.operation(), Operation::Parse);
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_read_json_nonexistent() {
+        let tmpdir = TempDir::new().unwrap();
+        let infile = InputArg::from_arg(tmpdir.child("nonexistent.json").path());
+        let e = infile.read_json::<Config>().unwrap_err();
+        assert_eq!(e.operation(), Operation::Open);
+    }
+
+    #[test]
+    fn test_write_json() {
+        let tmpdir = TempDir::new().unwrap();
+        let tmpfile = tmpdir.child("config.json");
+        let outfile = OutputArg::from_arg(tmpfile.path());
+        outfile.write_json(&config()).unwrap();
+        tmpfile.assert("{\"name\":\"widget\",\"count\":42}\n");
+    }
+
+    #[test]
+    fn test_write_json_pretty() {
+        let tmpdir = TempDir::new().unwrap();
+        let tmpfile = tmpdir.child("config.json");
+        let outfile = OutputArg::from_arg(tmpfile.path());
+        outfile.write_json_pretty(&config()).unwrap();
+        tmpfile.assert("{\n  \"name\": \"widget\",\n  \"count\": 42\n}\n");
+    }
+}
+
+#[cfg(feature = "toml")]
+mod toml {
+    use super::*;
+
+    #[test]
+    fn test_read_toml() {
+        let tmpdir = TempDir::new().unwrap();
+        let tmpfile = tmpdir.child("config.toml");
+        tmpfile
+            .write_str("name = \"widget\"\ncount = 42\n")
+            .unwrap();
+        let infile = InputArg::from_arg(tmpfile.path());
+        assert_eq!(infile.read_toml::<Config>().unwrap(), config());
+    }
+
+    #[test]
+    fn test_read_toml_invalid() {
+        let tmpdir = TempDir::new().unwrap();
+        let tmpfile = tmpdir.child("config.toml");
+        tmpfile.write_str("name = \"widget\"\ncount = \n").unwrap();
+        let infile = InputArg::from_arg(tmpfile.path());
+        let e = infile.read_toml::<Config>().unwrap_err();
+        assert_eq!(e.operation(), Operation::Parse);
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(e.input_arg(), Some(&infile));
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod yaml {
+    use super::*;
+
+    #[test]
+    fn test_read_yaml() {
+        let tmpdir = TempDir::new().unwrap();
+        let tmpfile = tmpdir.child("config.yaml");
+        tmpfile.write_str("name: widget\ncount: 42\n").unwrap();
+        let infile = InputArg::from_arg(tmpfile.path());
+        assert_eq!(infile.read_yaml::<Config>().unwrap(), config());
+    }
+
+    #[test]
+    fn test_read_yaml_invalid() {
+        let tmpdir = TempDir::new().unwrap();
+        let tmpfile = tmpdir.child("config.yaml");
+        tmpfile.write_str("name: widget\ncount: [\n").unwrap();
+        let infile = InputArg::from_arg(tmpfile.path());
+        let e = infile.read_yaml::<Config>().unwrap_err();
+        assert_eq!(e.operation(), Operation::Parse);
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(e.input_arg(), Some(&infile));
+    }
+
+    #[test]
+    fn test_read_yaml_directory() {
+        let tmpdir = TempDir::new().unwrap();
+        let infile = InputArg::from_arg(tmpdir.path());
+        let e = infile.read_yaml::<Config>().unwrap_err();
+        assert_eq!(e.operation(), Operation::Read);
+    }
+}
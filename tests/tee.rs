@@ -0,0 +1,98 @@
+#![cfg(test)]
+#![allow(missing_docs)]
+use assert_fs::TempDir;
+use assert_fs::prelude::*;
+use patharg::{Operation, OutputArg, TeeWriter};
+use std::io::Write;
+
+#[test]
+fn test_tee_to_files() {
+    let tmpdir = TempDir::new().unwrap();
+    let first = tmpdir.child("first.txt");
+    let second = tmpdir.child("second.txt");
+    second.write_str("Old content\n").unwrap();
+    let outputs = [
+        OutputArg::from_arg(first.path()),
+        OutputArg::from_arg(second.path()),
+    ];
+    let mut f = TeeWriter::create(&outputs).unwrap();
+    assert!(f.output_args().eq(&outputs));
+    writeln!(&mut f, "Line 1").unwrap();
+    writeln!(&mut f, "Line 2").unwrap();
+    f.flush().unwrap();
+    drop(f);
+    first.assert("Line 1\nLine 2\n");
+    second.assert("Line 1\nLine 2\n");
+}
+
+#[test]
+fn test_tee_append() {
+    let tmpdir = TempDir::new().unwrap();
+    let first = tmpdir.child("first.txt");
+    first.write_str("Old content\n").unwrap();
+    let second = tmpdir.child("second.txt");
+    let outputs = [
+        OutputArg::from_arg(first.path()),
+        OutputArg::from_arg(second.path()),
+    ];
+    let mut f = OutputArg::options()
+        .append(true)
+        .open_tee(&outputs)
+        .unwrap();
+    writeln!(&mut f, "New line").unwrap();
+    drop(f);
+    first.assert("Old content\nNew line\n");
+    second.assert("New line\n");
+}
+
+#[test]
+fn test_tee_open_error() {
+    let tmpdir = TempDir::new().unwrap();
+    let good = tmpdir.child("good.txt");
+    let bad = OutputArg::from_arg(tmpdir.child("nonexistent").child("bad.txt").path());
+    let outputs = [OutputArg::from_arg(good.path()), bad.clone()];
+    let e = TeeWriter::create(&outputs).unwrap_err();
+    assert_eq!(e.operation(), Operation::Create);
+    assert_eq!(e.output_arg(), Some(&bad));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_tee_write_error_names_target() {
+    let tmpdir = TempDir::new().unwrap();
+    let good = tmpdir.child("good.txt");
+    let full = OutputArg::from_arg("/dev/full");
+    let outputs = [OutputArg::from_arg(good.path()), full.clone()];
+    let mut f = TeeWriter::create(&outputs).unwrap();
+    let e = f.write_all(b"Data\n").unwrap_err();
+    let inner = e
+        .get_ref()
+        .and_then(|e| e.downcast_ref::<patharg::Error>())
+        .unwrap();
+    assert_eq!(inner.operation(), Operation::Write);
+    assert_eq!(inner.output_arg(), Some(&full));
+    good.assert("Data\n");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_tee_write_error_continues() {
+    let tmpdir = TempDir::new().unwrap();
+    let first = tmpdir.child("first.txt");
+    let last = tmpdir.child("last.txt");
+    let full = OutputArg::from_arg("/dev/full");
+    let outputs = [
+        OutputArg::from_arg(first.path()),
+        full.clone(),
+        OutputArg::from_arg(last.path()),
+    ];
+    let mut f = TeeWriter::create(&outputs).unwrap();
+    let e = f.write_all(b"Data\n").unwrap_err();
+    let inner = e
+        .get_ref()
+        .and_then(|e| e.downcast_ref::<patharg::Error>())
+        .unwrap();
+    assert_eq!(inner.output_arg(), Some(&full));
+    first.assert("Data\n");
+    last.assert("Data\n");
+}